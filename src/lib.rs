@@ -3,7 +3,12 @@ use colored::*;
 //use derive_more::{Add, Display, From, Into};
 use indextree;
 use std::cmp::PartialEq;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
+use std::sync::{Arc, Mutex};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 
 ///This is a toy parser/compiler loosely taking inspiration from [Elm Parser](https://package.elm-lang.org/packages/elm/parser/latest/Parser) with the following methods so far...
@@ -50,23 +55,37 @@ use unicode_segmentation::UnicodeSegmentation;
 /// ### Functions (perhaps these should be in userland?)
 ///[fn_var_assign (=)](#method.fn_var_assign),
 ///
-///[fn_var_sum (+)](#method.fn_var_sum)
+///[fn_var_sum (+)](#method.fn_var_sum), [fn_var_sub (-)](#method.fn_var_sub), [fn_var_mul (*)](#method.fn_var_mul), [fn_var_div (/)](#method.fn_var_div),
+///
+///[fn_str_concat (.)](#method.fn_str_concat),
+///
+///[fn_print (print)](#method.fn_print)
+///
+///[fn_scope_begin ({)](#method.fn_scope_begin), [fn_scope_end (})](#method.fn_scope_end)
 ///<br /><br />
 ///Parser is initialised once using [new](#method.new) for each string you wish to parse.<br />
 ///Then it is passed through all the parser functions you have defined<br />
 ///This is the current 'state' of the parser at any one time during its passage through all the parser functions
 ///- input_original: always contains the initial string supplied to [new](#method.new)
-///- input_remaining: is the current state of the remaining string to be parsed, as it passes through each parser function
+///- position: is the byte offset into input_original of the remaining string to be parsed, as it passes through each parser function.<br />
+///  The remaining string itself is borrowed from input_original with [input_remaining](#method.input_remaining), so consuming input never reallocates
 ///- output_arena: is a list of ParserElements (in an 'indextree' Arena) generated by your parser functions
 ///- output_arena_node_parent_id: refers to the current parent node in the indextree Arena
 ///- chomp: is the sub-string built up by a subgroup of the current parser functions.<br />
 ///  It can be cleared manually with [chomp_clear](#method.chomp_clear) and is usually used to build some fragment of a string for e.g. a variable name
 ///- success: is set to true or false by the current parser function. Currently, if a fail occurs, it is passed through all functions until the last one<br />
 ///  (TODO) use Results, and Panic during main parser functions
+///- error: the message and position of the failure which set success to false, see [last_error](#method.last_error).<br />
+///  Recorded even when display_errors is false, and not overwritten by the later functions the fail is passed through,
+///  or by a function which only failed because an inner parser did, see [fail_passing_on](#method.fail_passing_on)
+///- captured_errors: if set by [capture_errors](#method.capture_errors), the displayed errors are collected here instead of printed to stdout.<br />
+///  It is shared by all the clones of the parser, so errors from every attempt are collected in one place
+///- indents: a stack of indentation levels (in spaces) for indentation-sensitive languages, see [indent_push](#method.indent_push) and [prim_indent](#method.prim_indent)
+///- arena_backups: one for each combinator which is backtracking, e.g. [combi_first_success_of](#method.combi_first_success_of), so it can undo a failed attempt completely
 #[derive(Debug, Clone)]
 pub struct Parser {
     input_original: String,
-    input_remaining: String,
+    position: usize,
     language_arena: indextree::Arena<ParserFunctionTypeAndParam>,
     language_arena_node_parent_id: indextree::NodeId,
     output_arena: indextree::Arena<ParserElement>,
@@ -75,6 +94,177 @@ pub struct Parser {
     chomping: bool,
     success: bool,
     display_errors: bool,
+    error: Option<String>,
+    error_position: Option<usize>,
+    captured_errors: Option<Arc<Mutex<Vec<String>>>>,
+    indents: Vec<usize>,
+    custom_parsers: HashMap<String, ParserFunction>,
+    depth: usize,
+    max_depth: usize,
+    max_depth_exceeded: bool,
+    build_output: bool,
+    arena_backups: Vec<ParserArenaBackup>,
+}
+
+///A saved copy of the cheap parts of a [Parser](struct.Parser.html) state, made by [snapshot](struct.Parser.html#method.snapshot)<br />
+///so a user combinator can [restore](struct.Parser.html#method.restore) it to backtrack manually
+/// - position (i.e. input_remaining), chomp, chomping, success, the last error, the indents and the depth are saved
+/// - the language_arena and output_arena are NOT saved, so any elements appended since the snapshot are kept
+#[derive(Debug, Clone)]
+pub struct ParserSnapshot {
+    position: usize,
+    chomp: String,
+    chomping: bool,
+    success: bool,
+    error: Option<String>,
+    error_position: Option<usize>,
+    indents: Vec<usize>,
+    depth: usize,
+    max_depth_exceeded: bool,
+}
+
+///Where the current parent's children ended in one of the arenas when a checkpoint was made,
+///so the nodes appended since can be told apart from the ones which were already there
+#[derive(Debug, Clone, Copy)]
+struct ParserArenaMark {
+    parent_id: indextree::NodeId,
+    children: usize,
+    last_child_id: Option<indextree::NodeId>,
+}
+
+impl ParserArenaMark {
+    fn new<T>(arena: &indextree::Arena<T>, parent_id: indextree::NodeId) -> ParserArenaMark {
+        ParserArenaMark {
+            parent_id,
+            children: parent_id.children(arena).count(),
+            last_child_id: arena.get(parent_id).and_then(|node| node.last_child()),
+        }
+    }
+
+    ///Whether the node is (or is inside) a child appended to the marked parent since the mark was made
+    fn is_appended<T>(
+        self: &ParserArenaMark,
+        arena: &indextree::Arena<T>,
+        node_id: indextree::NodeId,
+    ) -> bool {
+        let child_option = node_id
+            .ancestors(arena)
+            .find(|id| arena.get(*id).and_then(|node| node.parent()) == Some(self.parent_id));
+        match (child_option, self.last_child_id) {
+            (Some(_), None) => true,
+            (Some(child_id), Some(last_child_id)) => {
+                child_id != last_child_id
+                    && child_id
+                        .preceding_siblings(arena)
+                        .any(|id| id == last_child_id)
+            }
+            _ => false,
+        }
+    }
+}
+
+///The arenas, as they were before the first change in place to a node which existed when a checkpoint was made,
+///e.g. a removed element, so a combinator can rollback a failed attempt.<br />
+///Nodes appended since the checkpoint are just removed instead, so it is only copied when it is needed
+#[derive(Clone)]
+struct ParserArenaBackup {
+    output_arena_mark: ParserArenaMark,
+    language_arena_mark: ParserArenaMark,
+    arenas: Option<
+        Arc<(
+            indextree::Arena<ParserElement>,
+            indextree::Arena<ParserFunctionTypeAndParam>,
+        )>,
+    >,
+}
+
+impl fmt::Debug for ParserArenaBackup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParserArenaBackup")
+            .field("output_arena_mark", &self.output_arena_mark)
+            .field("language_arena_mark", &self.language_arena_mark)
+            .field("copied", &self.arenas.is_some())
+            .finish()
+    }
+}
+
+///A [ParserSnapshot](struct.ParserSnapshot.html) and the level of its ParserArenaBackup in the parser's arena_backups, made by checkpoint
+#[derive(Debug)]
+struct ParserCheckpoint {
+    snap: ParserSnapshot,
+    level: usize,
+}
+
+///Removes any children (and their descendants) of the parent node after the first 'count', in either arena
+fn arena_truncate_children<T>(
+    arena: &mut indextree::Arena<T>,
+    parent_id: indextree::NodeId,
+    count: usize,
+) {
+    let children: Vec<indextree::NodeId> = parent_id.children(arena).skip(count).collect();
+    for child in children {
+        //remove the deepest nodes first, so none are re-attached to the parent
+        let descendants: Vec<indextree::NodeId> = child.descendants(arena).collect();
+        for node_id in descendants.into_iter().rev() {
+            node_id.remove(arena);
+        }
+    }
+}
+
+///An error found by [parse_all](struct.Parser.html#method.parse_all)
+/// - message: the [last_error](struct.Parser.html#method.last_error) when the statement failed
+/// - position: the byte offset in input_original where the failed statement started
+/// - line: the line number (starting from 1) of that position
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    message: String,
+    position: usize,
+    line: usize,
+}
+
+///A summary of the outcome of parsing, made by [summary](struct.Parser.html#method.summary), e.g. for tooling
+/// - element_count: how many elements are children of the current parent node, i.e. the top level after a whole parse
+/// - success: whether the parser is still succeeding
+/// - bytes_consumed: the byte offset into input_original reached, i.e. its length if all the input was parsed
+/// - error: the [last_error](struct.Parser.html#method.last_error), if any
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseSummary {
+    element_count: usize,
+    success: bool,
+    bytes_consumed: usize,
+    error: Option<String>,
+}
+
+impl ParseSummary {
+    pub fn element_count(self: &ParseSummary) -> usize {
+        self.element_count
+    }
+
+    pub fn success(self: &ParseSummary) -> bool {
+        self.success
+    }
+
+    pub fn bytes_consumed(self: &ParseSummary) -> usize {
+        self.bytes_consumed
+    }
+
+    pub fn error(self: &ParseSummary) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+impl ParseError {
+    pub fn message(self: &ParseError) -> &str {
+        self.message.as_str()
+    }
+
+    pub fn position(self: &ParseError) -> usize {
+        self.position
+    }
+
+    pub fn line(self: &ParseError) -> usize {
+        self.line
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -87,13 +277,27 @@ pub struct Parser {
 /// - what 'value' it should have depending on which are populated, here there are only 2 types<br />
 ///   - in64<br />
 ///   - float64<br />
-/// - var_name: a string for the name if it is a variable
+///   - string<br />
+///   - character<br />
+///   - boolean<br />
+/// - var_name: a string for the name if it is a variable<br />
+/// - var_type: the declared type if the variable has a type annotation, e.g. "x:int"<br />
+/// - reducer: for a Sum or BinaryOp, how [evaluate](struct.Parser.html#method.evaluate) combines its two children<br />
+/// - reducer_error: what evaluate reports if the reducer can't combine two Int64s, e.g. "division by zero or integer overflow", otherwise it is "integer overflow"<br /><br />
+///
+///Superseded by the simpler [ParserEl](struct.ParserEl.html), which any ParserElement can be converted into,
+///e.g. using [output_arena_els](struct.Parser.html#method.output_arena_els)
 pub struct ParserElement {
     el_type: Option<ParserElementType>,
     int64: Option<i64>,
     float64: Option<f64>,
     string: Option<String>,
+    character: Option<char>,
+    boolean: Option<bool>,
     var_name: Option<String>,
+    var_type: Option<ParserElementType>,
+    reducer: Option<ParserElementReducer>,
+    reducer_error: Option<String>,
 }
 #[derive(Debug, Clone, PartialEq)]
 pub enum ParserElementType {
@@ -101,6 +305,28 @@ pub enum ParserElementType {
     Float64,
     Var,
     Str,
+    Print,
+    Bool,
+    Scope,
+    Sum,
+    BinaryOp,
+    List,
+    Duration,
+    Char,
+    Null,
+}
+
+impl ParserElementType {
+    ///The type for a type annotation name, e.g. "int" in "x:int"
+    pub fn from_type_name(name: &str) -> Option<ParserElementType> {
+        match name {
+            "int" => Some(ParserElementType::Int64),
+            "float" => Some(ParserElementType::Float64),
+            "str" => Some(ParserElementType::Str),
+            "bool" => Some(ParserElementType::Bool),
+            _ => None,
+        }
+    }
 }
 
 impl ParserElement {
@@ -110,16 +336,153 @@ impl ParserElement {
             int64: None,
             float64: None,
             string: None,
+            character: None,
+            boolean: None,
             var_name: None,
+            var_type: None,
+            reducer: None,
+            reducer_error: None,
+        }
+    }
+
+    ///An Int64 element
+    pub fn new_int(val: i64) -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::Int64);
+        el.int64 = Some(val);
+        el
+    }
+
+    ///A Float64 element
+    pub fn new_float(val: f64) -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::Float64);
+        el.float64 = Some(val);
+        el
+    }
+
+    ///A Str element
+    pub fn new_str(val: String) -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::Str);
+        el.string = Some(val);
+        el
+    }
+
+    ///A Sum element, whose two children are the values to be added together by [evaluate](struct.Parser.html#method.evaluate)
+    pub fn new_sum() -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::Sum);
+        el.reducer = Some(ParserElement::checked_add);
+        el
+    }
+
+    ///A BinaryOp element, whose two children are combined by the reducer when [evaluate](struct.Parser.html#method.evaluate) is called,
+    ///with the symbol kept as its string, e.g. "*"
+    pub fn new_binary_op(symbol: &str, reducer: ParserElementReducer) -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::BinaryOp);
+        el.string = Some(symbol.to_string());
+        el.reducer = Some(reducer);
+        el
+    }
+
+    ///Two Int64s or two Float64s added together, or None for an integer overflow or any other types
+    pub fn checked_add(el1: &ParserElement, el2: &ParserElement) -> Option<ParserElement> {
+        ParserElement::checked_arithmetic(el1, el2, i64::checked_add, |val1, val2| val1 + val2)
+    }
+
+    ///The second of two Int64s or two Float64s subtracted from the first, or None for an integer overflow or any other types
+    pub fn checked_sub(el1: &ParserElement, el2: &ParserElement) -> Option<ParserElement> {
+        ParserElement::checked_arithmetic(el1, el2, i64::checked_sub, |val1, val2| val1 - val2)
+    }
+
+    ///Two Int64s or two Float64s multiplied together, or None for an integer overflow or any other types
+    pub fn checked_mul(el1: &ParserElement, el2: &ParserElement) -> Option<ParserElement> {
+        ParserElement::checked_arithmetic(el1, el2, i64::checked_mul, |val1, val2| val1 * val2)
+    }
+
+    ///The first of two Int64s or two Float64s divided by the second, or None for an integer division by zero or overflow, or any other types<br />
+    ///Int64s are divided like rust integers, i.e. "/ 7 2" is 3
+    pub fn checked_div(el1: &ParserElement, el2: &ParserElement) -> Option<ParserElement> {
+        ParserElement::checked_arithmetic(el1, el2, i64::checked_div, |val1, val2| val1 / val2)
+    }
+
+    fn checked_arithmetic(
+        el1: &ParserElement,
+        el2: &ParserElement,
+        int_op: fn(i64, i64) -> Option<i64>,
+        float_op: fn(f64, f64) -> f64,
+    ) -> Option<ParserElement> {
+        match (&el1.el_type, &el2.el_type) {
+            (Some(ParserElementType::Int64), Some(ParserElementType::Int64)) => {
+                int_op(el1.int64?, el2.int64?).map(ParserElement::new_int)
+            }
+            (Some(ParserElementType::Float64), Some(ParserElementType::Float64)) => Some(
+                ParserElement::new_float(float_op(el1.float64?, el2.float64?)),
+            ),
+            (_, _) => None,
         }
     }
+
+    ///A Bool element
+    pub fn new_bool(val: bool) -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::Bool);
+        el.boolean = Some(val);
+        el
+    }
+
+    ///A Null element, with no value, so it can be told apart from the root node (which has no el_type)
+    pub fn new_null() -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::Null);
+        el
+    }
+
+    ///A Char element
+    pub fn new_char(val: char) -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::Char);
+        el.character = Some(val);
+        el
+    }
+
+    ///A Duration element, of a number of seconds
+    pub fn new_duration(seconds: i64) -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::Duration);
+        el.int64 = Some(seconds);
+        el
+    }
+
+    ///A List element, whose children are the items in the list, e.g. made by [el_list](struct.Parser.html#method.el_list)
+    pub fn new_list() -> ParserElement {
+        let mut el = ParserElement::new();
+        el.el_type = Some(ParserElementType::List);
+        el
+    }
+
+    ///A Var element named var_name, holding whichever value the 'value' element has (if any)
+    pub fn new_var(var_name: String, value: ParserElement) -> ParserElement {
+        let mut el = value;
+        el.el_type = Some(ParserElementType::Var);
+        el.var_name = Some(var_name);
+        el
+    }
 }
 
-//TODO tryout this simpler parser element
-#[derive(Debug, Clone)]
+///A simpler [ParserElement](struct.ParserElement.html), with a single value instead of an Option for each type of value<br /><br />
+///
+/// - el_type: the type of thing it is<br />
+/// - value: which [ParserElValue](enum.ParserElValue.html) it has, if any<br />
+///   - a Var which hasn't been assigned a value yet, just has its name as a ParserElValue::Var<br />
+/// - var_name: a string for the name if it is a variable
+#[derive(Debug, Clone, PartialEq)]
 pub struct ParserEl {
     el_type: Option<ParserElementType>,
     value: Option<ParserElValue>,
+    var_name: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -127,6 +490,8 @@ pub enum ParserElValue {
     I64(i64),
     F64(f64),
     Str(String),
+    Char(char),
+    Bool(bool),
     Var(String),
 }
 
@@ -135,6 +500,90 @@ impl ParserEl {
         ParserEl {
             el_type: None,
             value: None,
+            var_name: None,
+        }
+    }
+
+    ///An Int64 element
+    pub fn new_int(val: i64) -> ParserEl {
+        ParserEl {
+            el_type: Some(ParserElementType::Int64),
+            value: Some(ParserElValue::I64(val)),
+            var_name: None,
+        }
+    }
+
+    ///A Float64 element
+    pub fn new_float(val: f64) -> ParserEl {
+        ParserEl {
+            el_type: Some(ParserElementType::Float64),
+            value: Some(ParserElValue::F64(val)),
+            var_name: None,
+        }
+    }
+
+    ///A Str element
+    pub fn new_str(val: String) -> ParserEl {
+        ParserEl {
+            el_type: Some(ParserElementType::Str),
+            value: Some(ParserElValue::Str(val)),
+            var_name: None,
+        }
+    }
+
+    ///A Char element
+    pub fn new_char(val: char) -> ParserEl {
+        ParserEl {
+            el_type: Some(ParserElementType::Char),
+            value: Some(ParserElValue::Char(val)),
+            var_name: None,
+        }
+    }
+
+    ///A Var element named var_name, holding the 'value' (or just its name if it has no value yet)
+    pub fn new_var(var_name: String, value: Option<ParserElValue>) -> ParserEl {
+        ParserEl {
+            el_type: Some(ParserElementType::Var),
+            value: value.or(Some(ParserElValue::Var(var_name.clone()))),
+            var_name: Some(var_name),
+        }
+    }
+
+    pub fn el_type(self: &ParserEl) -> Option<&ParserElementType> {
+        self.el_type.as_ref()
+    }
+
+    pub fn value(self: &ParserEl) -> Option<&ParserElValue> {
+        self.value.as_ref()
+    }
+
+    pub fn var_name(self: &ParserEl) -> Option<&str> {
+        self.var_name.as_deref()
+    }
+}
+
+impl From<ParserElement> for ParserEl {
+    fn from(el: ParserElement) -> ParserEl {
+        let value = match (
+            el.int64,
+            el.float64,
+            el.string,
+            el.character,
+            el.boolean,
+            el.var_name.clone(),
+        ) {
+            (Some(val), _, _, _, _, _) => Some(ParserElValue::I64(val)),
+            (_, Some(val), _, _, _, _) => Some(ParserElValue::F64(val)),
+            (_, _, Some(val), _, _, _) => Some(ParserElValue::Str(val)),
+            (_, _, _, Some(val), _, _) => Some(ParserElValue::Char(val)),
+            (_, _, _, _, Some(val), _) => Some(ParserElValue::Bool(val)),
+            (_, _, _, _, _, Some(var_name)) => Some(ParserElValue::Var(var_name)),
+            _ => None,
+        };
+        ParserEl {
+            el_type: el.el_type,
+            value,
+            var_name: el.var_name,
         }
     }
 }
@@ -145,8 +594,7 @@ pub enum ParserFunctionType {
     TakesParser(ParserFunction), //e.g. primitive except prim_word, element, function
     TakesParserWord(ParserFunctionString), //e.g. prim_word
     TakesParserFn(ParserFunctionParserFunction), //e.g. simple combinator like combi_parser_one_or_more
-                                                 //TakesParserVecFn(ParserFunction, ParserFunctionParam::Avec(<Vec<ParserFunction>>)), //e.g. combi_until_first_do_second
-                                                 //TakesParserBVecFn(ParserFunction, Vec<ParserFunction>), //e.g. combi_until_first_do_second
+    TakesParserVecFn(ParserFunctionParserVecFunction), //e.g. combi_first_success_of
 }
 
 ///None, String, Parser, VecParser
@@ -161,7 +609,85 @@ pub enum ParserFunctionParam {
 pub type ParserFunction = fn(Parser) -> Parser;
 pub type ParserFunctionString = fn(Parser, &str) -> Parser;
 pub type ParserFunctionParserFunction = fn(Parser, ParserFunction) -> Parser;
+pub type ParserFunctionParserVecFunction = fn(Parser, &Vec<ParserFunction>) -> Parser;
 pub type ParserFunctionTypeAndParam = (ParserFunctionType, ParserFunctionParam);
+pub type ParserElementReducer = fn(&ParserElement, &ParserElement) -> Option<ParserElement>;
+
+///The word matched by [el_null](struct.Parser.html#method.el_null), change it to e.g. "nil" or "none" for your data format
+pub const NULL_WORD: &str = "null";
+
+///A class of characters matched by [prim_class](struct.Parser.html#method.prim_class), using the char::is_* predicates
+/// - Digit: 0 to 9
+/// - Alpha: any unicode letter, e.g. "a" or "é"
+/// - Alnum: any unicode letter or number
+/// - Space: any unicode whitespace, e.g. a space, tab or eol
+/// - Punct: ASCII punctuation, e.g. "!" or "_"
+/// - Upper: any unicode uppercase letter
+/// - Lower: any unicode lowercase letter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CharClass {
+    Digit,
+    Alpha,
+    Alnum,
+    Space,
+    Punct,
+    Upper,
+    Lower,
+}
+
+impl CharClass {
+    ///Whether the character is in this class
+    pub fn matches(self: &CharClass, c: char) -> bool {
+        match self {
+            CharClass::Digit => c.is_ascii_digit(),
+            CharClass::Alpha => c.is_alphabetic(),
+            CharClass::Alnum => c.is_alphanumeric(),
+            CharClass::Space => c.is_whitespace(),
+            CharClass::Punct => c.is_ascii_punctuation(),
+            CharClass::Upper => c.is_uppercase(),
+            CharClass::Lower => c.is_lowercase(),
+        }
+    }
+}
+
+///A type which can be parsed, through the generic [element](struct.Parser.html#method.element), e.g. parser.element::&lt;i64&gt;()<br />
+///It is implemented for the rust types of the built-in elements, i.e. i64 ([el_int](struct.Parser.html#method.el_int)),
+///f64 ([el_float](struct.Parser.html#method.el_float)), String ([el_str](struct.Parser.html#method.el_str)),
+///char ([el_char](struct.Parser.html#method.el_char)) and bool ([el_bool](struct.Parser.html#method.el_bool)),
+///and you can implement it for your own types, e.g. by appending whichever ParserElement suits them best
+pub trait Parseable: Sized {
+    fn parse(p: Parser) -> Parser;
+}
+
+impl Parseable for i64 {
+    fn parse(p: Parser) -> Parser {
+        p.el_int()
+    }
+}
+
+impl Parseable for f64 {
+    fn parse(p: Parser) -> Parser {
+        p.el_float()
+    }
+}
+
+impl Parseable for String {
+    fn parse(p: Parser) -> Parser {
+        p.el_str()
+    }
+}
+
+impl Parseable for char {
+    fn parse(p: Parser) -> Parser {
+        p.el_char()
+    }
+}
+
+impl Parseable for bool {
+    fn parse(p: Parser) -> Parser {
+        p.el_bool()
+    }
+}
 
 ///quick and dirty helper function to Debug function names
 //https://users.rust-lang.org/t/get-the-name-of-the-function-a-function-pointer-points-to/14930
@@ -181,6 +707,7 @@ impl fmt::Debug for ParserFunctionType {
             }
             ParserFunctionType::TakesParserWord(_) => write!(f, "TakesParserWord"),
             ParserFunctionType::TakesParserFn(_) => write!(f, "TakesParserFn"),
+            ParserFunctionType::TakesParserVecFn(_) => write!(f, "TakesParserVecFn"),
         }
     }
 }
@@ -212,7 +739,7 @@ impl Parser {
 
         let new_parser = Parser {
             input_original: input_string.to_string(),
-            input_remaining: input_string.to_string(),
+            position: 0,
             chomp: "".to_string(),
             chomping: true,
             language_arena,
@@ -221,22 +748,222 @@ impl Parser {
             output_arena_node_parent_id,
             success: true,
             display_errors: true,
+            error: None,
+            error_position: None,
+            captured_errors: None,
+            indents: vec![],
+            custom_parsers: HashMap::new(),
+            depth: 0,
+            max_depth: 100,
+            max_depth_exceeded: false,
+            build_output: true,
+            arena_backups: vec![],
         };
         new_parser
     }
 
+    ///Initialises a new parser with the contents of a file<br />
+    ///The whole file is read into input_original, but consuming it only moves the position, so large files don't get copied over and over
+    pub fn from_file(path: &std::path::Path) -> std::io::Result<Parser> {
+        let input_string = std::fs::read_to_string(path)?;
+        Ok(Parser::new(&input_string))
+    }
+
+    ///As [from_file](#method.from_file), but first normalizes all line endings (\r\n or \r) to \n
+    pub fn from_file_normalizing_eols(path: &std::path::Path) -> std::io::Result<Parser> {
+        let input_string = std::fs::read_to_string(path)?;
+        Ok(Parser::new_normalized(&input_string))
+    }
+
+    ///Initialises a new parser like [new](#method.new), but first normalizes all line endings (\r\n or \r) to \n,
+    ///so the newline primitives only ever see \n<br />
+    ///Beware, input_original then holds the normalized text, so error positions refer to that rather than the text passed in
+    pub fn new_normalized(input_string: &str) -> Parser {
+        let input_string = input_string.replace("\r\n", "\n").replace('\r', "\n");
+        Parser::new(&input_string)
+    }
+
+    ///Initialises a new parser like [new](#method.new), but reserves 'chomp_cap' bytes for the chomp up front,
+    ///so building up a long chomp (e.g. a long string or number) doesn't keep reallocating
+    pub fn with_capacity(input_string: &str, chomp_cap: usize) -> Parser {
+        let mut new_parser = Parser::new(input_string);
+        new_parser.chomp = String::with_capacity(chomp_cap);
+        new_parser
+    }
+
+    ///Initialises a new parser like [new](#method.new), but with a different 'max_depth' (default 100),
+    ///i.e. how deeply a recursive function like [fn_var_sum](#method.fn_var_sum) can nest before it fails, rather than overflowing the stack
+    pub fn with_max_depth(input_string: &str, max_depth: usize) -> Parser {
+        let mut new_parser = Parser::new(input_string);
+        new_parser.max_depth = max_depth;
+        new_parser
+    }
+
+    ///Initialises a new parser like [new](#method.new), but which doesn't build the output_arena,
+    ///e.g. to check whether some input is well-formed without using memory for elements you won't read<br />
+    ///Beware, this is only for [Parser primitives](#parser-primitives), [Parser combinators](#parser-combinators) and [Parser Elements](#parser-elements),
+    ///because the fn_ functions (e.g. [fn_var_assign](#method.fn_var_assign)) need to read back the elements they appended, so will fail
+    pub fn new_validating(input_string: &str) -> Parser {
+        let mut new_parser = Parser::new(input_string);
+        new_parser.build_output = false;
+        new_parser
+    }
+
     ///Defines the parser to run, then runs it on the initialised parser from new
     ///for now it only contains a few things...
     ///'fn_var_assign' which itself calls sub-parsers like el_int, el_float, fn_var_sum
-    ///'prim_eols' to allow separating the variable assignments
+    ///'fn_print' to output the value of a variable
+    ///'fn_scope_begin' and 'fn_scope_end' to scope variables within curly brackets
+    ///'prim_comment' and 'prim_block_comment' to ignore comments, e.g. "// set x" or "/* set x */"
+    ///'prim_eols' to allow separating the variable assignments<br />
+    ///each statement ends with [prim_statement_end](#method.prim_statement_end), so they can also be separated by ";", e.g. "= x 1; = y 2"<br />
+    ///a leading byte order mark is skipped first, see [prim_bom](#method.prim_bom)
     pub fn parse(mut self: Parser) -> Parser {
-        while self.success && self.input_remaining.len() > 0 {
-            self =
-                self.combi_first_success_of(&[Parser::fn_var_assign, Parser::prim_eols].to_vec());
+        self = self.prim_bom();
+        while self.success && self.input_remaining().len() > 0 {
+            self = self.parse_statement();
         }
         self
     }
 
+    ///Appends more input to be parsed, e.g. the next chunk of data arriving over a socket, to be parsed by [parse_fed](#method.parse_fed)
+    pub fn feed(self: &mut Parser, more: &str) {
+        self.input_original.push_str(more);
+    }
+
+    ///Like [parse](#method.parse), but for input arriving in chunks by [feed](#method.feed),
+    ///so it only parses the complete lines (i.e. up to the last eol) fed so far, leaving a partial last line until more is fed<br />
+    ///Returns the parser, and whether more input is needed to finish the last line<br />
+    ///Beware, a statement over several lines (e.g. a block comment) must be fed whole, and at the real end of the input
+    ///call [parse](#method.parse) instead, to finish a last line which has no eol
+    pub fn parse_fed(mut self: Parser) -> (Parser, bool) {
+        let complete_length = match self.input_remaining().rfind(['\r', '\n']) {
+            Some(i) => i + 1,
+            None => 0,
+        };
+        let partial = self
+            .input_original
+            .split_off(self.position + complete_length);
+        while self.success && !self.input_remaining().is_empty() {
+            self = self.parse_statement();
+        }
+        self.input_original.push_str(partial.as_str());
+        let needs_more = self.success && !partial.is_empty();
+        (self, needs_more)
+    }
+
+    ///Parses a single statement (or comment, or eols) of the toy language, as used by [parse](#method.parse) and [parse_all](#method.parse_all)
+    pub fn parse_statement(self: Parser) -> Parser {
+        self.combi_first_success_of(
+            &[
+                Parser::fn_var_assign,
+                Parser::fn_print,
+                Parser::fn_scope_begin,
+                Parser::fn_scope_end,
+                Parser::prim_comment,
+                Parser::prim_block_comment,
+                Parser::prim_eols,
+            ]
+            .to_vec(),
+        )
+    }
+
+    ///Like [parse](#method.parse), but doesn't stop at the first error, e.g. for an IDE which wants to show them all<br />
+    ///When a statement fails, a [ParseError](struct.ParseError.html) is recorded, and it skips to the next line to carry on.<br />
+    ///Returns the elements that did parse (i.e. the children of the current parent node), and every error
+    pub fn parse_all(mut self: Parser) -> (Vec<ParserElement>, Vec<ParseError>) {
+        let mut errors: Vec<ParseError> = vec![];
+        self = self.prim_bom();
+        while !self.input_remaining().is_empty() {
+            let position = self.position;
+            self = self.parse_statement();
+            if !self.success {
+                errors.push(ParseError {
+                    message: self.last_error().unwrap_or("parse_all").to_string(),
+                    position,
+                    line: self.line_number(position),
+                });
+                //recover by skipping the rest of the line
+                self.success = true;
+                self.error = None;
+                self.error_position = None;
+                let length = self
+                    .input_remaining()
+                    .find(['\r', '\n'])
+                    .unwrap_or_else(|| self.input_remaining().len());
+                self.position += length;
+                self = self.combi_optional(Parser::prim_eols);
+            }
+        }
+        let arena = &self.output_arena;
+        let elements = self
+            .output_arena_node_parent_id
+            .children(arena)
+            .filter_map(|node_id| arena.get(node_id))
+            .map(|node| node.get().clone())
+            .collect();
+        (elements, errors)
+    }
+
+    ///The line number (starting from 1) of a position (byte offset) in input_original, counting \r\n, \n or \r as one line ending
+    pub fn line_number(self: &Parser, position: usize) -> usize {
+        self.input_original[..position]
+            .replace("\r\n", "\n")
+            .matches(['\r', '\n'])
+            .count()
+            + 1
+    }
+
+    ///Folds the structure kept in the output_arena into concrete values, e.g. each Sum built by [fn_var_sum](#method.fn_var_sum)
+    ///becomes the Int64 or Float64 of its two children added together, and a Var holding a Sum takes its value<br />
+    ///Fails if any can't be evaluated, e.g. an integer overflow, leaving the output_arena as it was
+    pub fn evaluate(mut self: Parser) -> Parser {
+        if self.success {
+            let arena = &self.output_arena;
+            let mut node_ids: Vec<indextree::NodeId> = vec![];
+            if let Some(root_id) = self.output_arena_node_parent_id.ancestors(arena).last() {
+                node_ids = root_id
+                    .descendants(arena)
+                    .filter(|node_id| {
+                        let has_children = node_id.children(arena).next().is_some();
+                        match arena.get(*node_id).map(|node| &node.get().el_type) {
+                            Some(Some(ParserElementType::Sum)) => true,
+                            Some(Some(ParserElementType::BinaryOp)) => true,
+                            Some(Some(ParserElementType::Var)) => has_children,
+                            Some(Some(ParserElementType::Print)) => has_children,
+                            _ => false,
+                        }
+                    })
+                    .collect();
+            }
+            //the deepest first, so each node's children are already values
+            let mut evaluated = self.clone();
+            evaluated.arenas_before_edit(&node_ids, &[]);
+            for node_id in node_ids.into_iter().rev() {
+                match evaluated.output_arena_evaluate_node(node_id) {
+                    Ok(el) => {
+                        let arena = &mut evaluated.output_arena;
+                        let children: Vec<indextree::NodeId> = node_id.children(arena).collect();
+                        for child_id in children {
+                            child_id.remove(arena);
+                        }
+                        if let Some(node) = arena.get_mut(node_id) {
+                            *node.get_mut() = el;
+                        }
+                    }
+                    Err(error) => {
+                        self.success = false;
+                        self.record_error(error.as_str());
+                        return self;
+                    }
+                }
+            }
+            evaluated
+        } else {
+            self
+        }
+    }
+
     ///Initialises and runs the supplied parser functions (as a closure) on a supplied string
     ///
     ///### Example
@@ -278,9 +1005,32 @@ impl Parser {
     }
 
     pub fn new_and_parse_aliases(input_string: &str, parser_lang_string: &str) -> Parser {
+        Parser::new_and_parse_aliases_with_custom_parsers(
+            input_string,
+            parser_lang_string,
+            &HashMap::new(),
+        )
+    }
+
+    ///As [new_and_parse_aliases](#method.new_and_parse_aliases), but the parser_lang_string can also use your own parsers by name
+    ///```
+    ///use rust_learning_parser_combinators::{Parser, ParserFunction};
+    ///use std::collections::HashMap;
+    ///fn prim_x(self_: Parser) -> Parser { self_.prim_word("x") }
+    ///let mut custom_parsers: HashMap<String, ParserFunction> = HashMap::new();
+    ///custom_parsers.insert("x".to_string(), prim_x);
+    ///let result = Parser::new_and_parse_aliases_with_custom_parsers("x1", "x#", &custom_parsers);
+    ///assert_eq!(result.input_remaining(), "");
+    ///```
+    pub fn new_and_parse_aliases_with_custom_parsers(
+        input_string: &str,
+        parser_lang_string: &str,
+        custom_parsers: &HashMap<String, ParserFunction>,
+    ) -> Parser {
         //first, parse the parser_lang_string to get the series of your parser instructions
         let mut parser_lang: Parser = Parser::new(parser_lang_string);
-        while parser_lang.success && parser_lang.input_remaining.len() > 0 {
+        parser_lang.custom_parsers = custom_parsers.clone();
+        while parser_lang.success && parser_lang.input_remaining().len() > 0 {
             parser_lang = parser_lang.lang_one_of_all_lang_parsers();
             parser_lang
                 .clone()
@@ -324,6 +1074,18 @@ impl Parser {
                     }
                     _ => (),
                 },
+                ParserFunctionType::TakesParserFn(fun) => match param_option {
+                    ParserFunctionParam::ParserFn(inner) => {
+                        parser = fun(parser, *inner);
+                    }
+                    _ => (),
+                },
+                ParserFunctionType::TakesParserVecFn(fun) => match param_option {
+                    ParserFunctionParam::VecParserFn(inners) => {
+                        parser = fun(parser, inners);
+                    }
+                    _ => (),
+                },
                 _ => (),
             }
         }
@@ -331,49 +1093,327 @@ impl Parser {
         parser
     }
 
-    pub fn display_error(self: &Parser, from: &str) {
-        //only display a short 100 char excerpt of remaining string
-        let mut length = self.input_remaining.len();
-        let position = self.input_original.len() - length;
-        if length > 100 {
-            length = 100;
-        }
-        if self.display_errors {
-            println!(
-                "\r\n{}\r\n{} at {} position:{}\r\n{}\r\n{}\r\n{:?}\r\n{}",
-                "vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv"
-                    .yellow(),
-                "Parser Error".yellow(),
-                from.red(),
-                position,
-                self.input_remaining.get(0..length).unwrap(),
-                "Current Parser state looks like this:".yellow(),
-                self,
-                "^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^"
-                    .yellow(),
-            );
+    ///Records the error, so it can be read back with [last_error](#method.last_error), then displays it if display_errors is true<br />
+    ///Only for the function which actually failed, see [fail_passing_on](#method.fail_passing_on) for one which failed because an inner parser did
+    pub fn record_error(self: &mut Parser, from: &str) {
+        self.error = Some(from.to_string());
+        self.error_position = Some(self.position);
+        self.display_error(from);
+    }
+
+    ///Fails, keeping the error of the inner parser which caused it, e.g. "el_var - invalid name" for "= 1x 2"<br />
+    ///as long as it got past the start, otherwise it records 'from', e.g. "fn_var_assign" for "x"<br />
+    ///'from' is displayed either way, but the error itself is kept from the function which actually failed
+    pub fn fail_passing_on(self: Parser, failed: &Parser, from: &str) -> Parser {
+        self.fail_passing_on_error(failed.error.clone(), failed.error_position, from)
+    }
+
+    ///As [fail_passing_on](#method.fail_passing_on), for when the failed attempt has already been undone, so only its error is left
+    fn fail_passing_on_error(
+        mut self: Parser,
+        error: Option<String>,
+        error_position: Option<usize>,
+        from: &str,
+    ) -> Parser {
+        self.success = false;
+        let is_new_error = error_position != self.error_position || error != self.error;
+        if is_new_error && error_position > Some(self.position) {
+            self.error = error;
+            self.error_position = error_position;
+            self.display_error(from);
+        } else {
+            self.record_error(from);
         }
+        self
     }
 
-    ///Clears the current `chomp` value back to an empty string
-    pub fn chomp_clear(mut self: Parser) -> Parser {
-        self.chomp = "".to_string();
+    ///Collects the displayed errors, to read with [captured_errors](#method.captured_errors), instead of printing them to stdout,
+    ///e.g. for an application which manages its own logging<br />
+    ///Each is the error and its position, e.g. "el_int at position:4"
+    pub fn capture_errors(mut self: Parser) -> Parser {
+        self.captured_errors = Some(Arc::new(Mutex::new(vec![])));
         self
     }
 
-    pub fn get_parser_function_by_name(name: String) -> ParserFunction {
-        match name.as_str() {
-            ">" => Parser::lang_prim_next,
-            "\"" => Parser::lang_prim_quote,
-            //TODO handle prim_word
-            "@" => Parser::lang_prim_char,
-            "#" => Parser::lang_prim_digit,
-            "," => Parser::lang_prim_eols,
-            "." => Parser::lang_prim_eof,
-            ";" => Parser::lang_prim_eols_or_eof,
-            //TODO handle combinators
-            //TODO handle elements
-            _ => Parser::lang_prim_eof,
+    ///The errors collected since [capture_errors](#method.capture_errors) was called, oldest first, or none if it wasn't
+    pub fn captured_errors(self: &Parser) -> Vec<String> {
+        match &self.captured_errors {
+            Some(captured_errors) => match captured_errors.lock() {
+                Ok(errors) => errors.clone(),
+                _ => vec![],
+            },
+            None => vec![],
+        }
+    }
+
+    ///Displays the error if display_errors is true, but without recording it<br />
+    ///Used when a function is only passing on an earlier fail, so the original error is kept
+    pub fn display_error(self: &Parser, from: &str) {
+        //only display a short 100 char excerpt of remaining string
+        let mut length = self.input_remaining().len();
+        let position = self.position;
+        if length > 100 {
+            length = 100;
+        }
+        if self.display_errors {
+            match &self.captured_errors {
+                Some(captured_errors) => {
+                    if let Ok(mut errors) = captured_errors.lock() {
+                        errors.push(format!("{} at position:{}", from, position));
+                    }
+                }
+                None => println!(
+                    "\r\n{}\r\n{} at {} position:{}\r\n{}\r\n{}\r\n{:?}\r\n{}",
+                    "vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv"
+                        .yellow(),
+                    "Parser Error".yellow(),
+                    from.red(),
+                    position,
+                    self.input_remaining().get(0..length).unwrap(),
+                    "Current Parser state looks like this:".yellow(),
+                    self,
+                    "^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^"
+                        .yellow(),
+                ),
+            }
+        }
+    }
+
+    ///Saves the current position (i.e. input_remaining), chomp, chomping, success, the last error, the indents and the depth, to [restore](#method.restore) later<br />
+    ///The arenas are not saved
+    pub fn snapshot(self: &Parser) -> ParserSnapshot {
+        ParserSnapshot {
+            position: self.position,
+            chomp: self.chomp.clone(),
+            chomping: self.chomping,
+            success: self.success,
+            error: self.error.clone(),
+            error_position: self.error_position,
+            indents: self.indents.clone(),
+            depth: self.depth,
+            max_depth_exceeded: self.max_depth_exceeded,
+        }
+    }
+
+    ///Rewinds position (i.e. input_remaining), chomp, chomping, success, the last error, the indents and the depth to a previous [snapshot](#method.snapshot)<br />
+    ///The arenas are left alone, so remove any unwanted elements yourself
+    pub fn restore(mut self: Parser, snap: ParserSnapshot) -> Parser {
+        self.position = snap.position;
+        //keep the chomp's capacity
+        self.chomp.clear();
+        self.chomp.push_str(snap.chomp.as_str());
+        self.chomping = snap.chomping;
+        self.success = snap.success;
+        self.error = snap.error;
+        self.error_position = snap.error_position;
+        self.indents = snap.indents;
+        self.depth = snap.depth;
+        self.max_depth_exceeded = snap.max_depth_exceeded;
+        self
+    }
+
+    ///A [snapshot](#method.snapshot) which can also undo any changes to the arenas, for a combinator to [rollback](#method.rollback) a failed attempt,
+    ///as if it had tried it on a clone of the whole parser<br />
+    ///Appended nodes are just removed again, and the arenas are only copied if an attempt changes a node which was already there, e.g. removes it<br />
+    ///It must be [released](#method.release) once it is no longer needed
+    fn checkpoint(self: &mut Parser) -> ParserCheckpoint {
+        let level = self.arena_backups.len();
+        self.arena_backups.push(ParserArenaBackup {
+            output_arena_mark: ParserArenaMark::new(
+                &self.output_arena,
+                self.output_arena_node_parent_id,
+            ),
+            language_arena_mark: ParserArenaMark::new(
+                &self.language_arena,
+                self.language_arena_node_parent_id,
+            ),
+            arenas: None,
+        });
+        ParserCheckpoint {
+            snap: self.snapshot(),
+            level,
+        }
+    }
+
+    ///Undoes everything since the [checkpoint](#method.checkpoint), including the elements appended, removed or changed, so the next attempt can start from the same place<br />
+    ///The checkpoint is still open afterwards
+    fn rollback(mut self: Parser, checkpoint: &ParserCheckpoint) -> Parser {
+        self = self.restore(checkpoint.snap.clone());
+        //any checkpoints opened since, but not released, are finished with too
+        self.arena_backups.truncate(checkpoint.level + 1);
+        if let Some(backup) = self.arena_backups.get_mut(checkpoint.level) {
+            if let Some(arenas) = backup.arenas.take() {
+                let (output_arena, language_arena) =
+                    Arc::try_unwrap(arenas).unwrap_or_else(|arenas| (*arenas).clone());
+                self.output_arena = output_arena;
+                self.language_arena = language_arena;
+            }
+            let output_arena_mark = backup.output_arena_mark;
+            let language_arena_mark = backup.language_arena_mark;
+            self.output_arena_node_parent_id = output_arena_mark.parent_id;
+            self.language_arena_node_parent_id = language_arena_mark.parent_id;
+            arena_truncate_children(
+                &mut self.output_arena,
+                output_arena_mark.parent_id,
+                output_arena_mark.children,
+            );
+            arena_truncate_children(
+                &mut self.language_arena,
+                language_arena_mark.parent_id,
+                language_arena_mark.children,
+            );
+        }
+        self
+    }
+
+    ///Closes the [checkpoint](#method.checkpoint), keeping everything since, e.g. after a successful attempt
+    fn release(mut self: Parser, checkpoint: ParserCheckpoint) -> Parser {
+        self.arena_backups.truncate(checkpoint.level);
+        self
+    }
+
+    ///Must be called before any nodes in the arenas are changed in place, rather than appended, e.g. removed or moved,
+    ///so an open [checkpoint](#method.checkpoint) can [rollback](#method.rollback) the change<br />
+    ///The arenas are only copied if one of the nodes was already there when a checkpoint was made
+    fn arenas_before_edit(
+        self: &mut Parser,
+        output_node_ids: &[indextree::NodeId],
+        language_node_ids: &[indextree::NodeId],
+    ) {
+        let output_arena = &self.output_arena;
+        let language_arena = &self.language_arena;
+        let is_needed = self.arena_backups.iter().any(|backup| {
+            backup.arenas.is_none()
+                && (output_node_ids
+                    .iter()
+                    .any(|id| !backup.output_arena_mark.is_appended(output_arena, *id))
+                    || language_node_ids
+                        .iter()
+                        .any(|id| !backup.language_arena_mark.is_appended(language_arena, *id)))
+        });
+        if is_needed {
+            //nothing has been changed in place since any checkpoint without a copy yet, so they can all share this one
+            let arenas = Arc::new((self.output_arena.clone(), self.language_arena.clone()));
+            for backup in self.arena_backups.iter_mut() {
+                if backup.arenas.is_none() {
+                    backup.arenas = Some(arenas.clone());
+                }
+            }
+        }
+    }
+
+    ///The message of the error which caused the parser to fail, e.g. "el_int", even if display_errors is false<br />
+    ///Only the function which actually failed records it, not the later functions the fail is passed through
+    pub fn last_error(self: &Parser) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    ///A [ParseSummary](struct.ParseSummary.html) of the element count, success, bytes consumed and last error, all in one value
+    pub fn summary(self: &Parser) -> ParseSummary {
+        ParseSummary {
+            element_count: self
+                .output_arena_node_parent_id
+                .children(&self.output_arena)
+                .count(),
+            success: self.success,
+            bytes_consumed: self.position,
+            error: self.error.clone(),
+        }
+    }
+
+    ///The position (byte offset into input_original) where the [last_error](#method.last_error) occurred
+    pub fn last_error_position(self: &Parser) -> Option<usize> {
+        self.error_position
+    }
+
+    ///The remaining string still to be parsed, borrowed from input_original at the current position
+    pub fn input_remaining(self: &Parser) -> &str {
+        &self.input_original[self.position..]
+    }
+
+    ///The string parsed so far, i.e. input_original up to the current position, e.g. for diagnostics<br />
+    ///Unlike the chomp, which is cleared after each element, this is everything since the parser was created
+    pub fn consumed(self: &Parser) -> &str {
+        &self.input_original[..self.position]
+    }
+
+    ///The number of graphemes (i.e. user-perceived characters) in the input_remaining, e.g. for a progress indicator<br />
+    ///unlike input_remaining().len() which counts bytes, so "é" is 1 grapheme but 2 bytes
+    pub fn remaining_graphemes(self: &Parser) -> usize {
+        self.input_remaining().graphemes(true).count()
+    }
+
+    ///Runs 'f' only if the parser has succeeded so far, otherwise passes the failed parser straight through<br />
+    ///i.e. the `if self.success { ... } else { self }` which most parser functions start with
+    pub fn and_then<F>(self: Parser, f: F) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            f(self)
+        } else {
+            self
+        }
+    }
+
+    ///Whether the input_remaining starts with 'word', without consuming anything, e.g. to decide which parser to try next<br />
+    ///A cheap read-only check, rather than running [prim_word](#method.prim_word) on a clone
+    pub fn starts_with(self: &Parser, word: &str) -> bool {
+        self.input_remaining().starts_with(word)
+    }
+
+    ///Pushes a new indentation level (in spaces) onto the indents stack, e.g. when entering an indented block
+    pub fn indent_push(mut self: Parser, spaces: usize) -> Parser {
+        self.indents.push(spaces);
+        self
+    }
+
+    ///Pops the current indentation level off the indents stack, e.g. when leaving an indented block
+    pub fn indent_pop(mut self: Parser) -> Parser {
+        self.indents.pop();
+        self
+    }
+
+    ///The current indentation level (in spaces) from the top of the indents stack, or 0 if it is empty
+    pub fn indent_current(self: &Parser) -> usize {
+        *self.indents.last().unwrap_or(&0)
+    }
+
+    ///Clears the current `chomp` value back to an empty string
+    pub fn chomp_clear(mut self: Parser) -> Parser {
+        self.chomp.clear();
+        self
+    }
+
+    ///Succeeds again after a fail, clearing the [last_error](#method.last_error), so you can try an alternative parse<br />
+    ///The position (i.e. input_remaining) and chomp are left as they are, so use [snapshot](#method.snapshot) and [restore](#method.restore) to rewind them
+    pub fn clear_error(mut self: Parser) -> Parser {
+        self.success = true;
+        self.error = None;
+        self.error_position = None;
+        self
+    }
+
+    ///The lang_ parser for an alias, e.g. ">" for [lang_prim_next](#method.lang_prim_next), or None if the alias is unknown
+    pub fn get_parser_function_by_name(name: String) -> Option<ParserFunction> {
+        match name.as_str() {
+            ">" => Some(Parser::lang_prim_next),
+            "\"" => Some(Parser::lang_prim_quote),
+            //a quoted word, e.g. 'test'
+            "'" => Some(Parser::lang_prim_word),
+            "@" => Some(Parser::lang_prim_char),
+            "#" => Some(Parser::lang_prim_digit),
+            "," => Some(Parser::lang_prim_eols),
+            "." => Some(Parser::lang_prim_eof),
+            ";" => Some(Parser::lang_prim_eols_or_eof),
+            "1+" => Some(Parser::lang_combi_one_or_more),
+            "??" => Some(Parser::lang_combi_optional),
+            "[" => Some(Parser::lang_combi_first_success_of),
+            "$str" => Some(Parser::lang_el_str),
+            "$int" => Some(Parser::lang_el_int),
+            "$flt" => Some(Parser::lang_el_float),
+            "$var" => Some(Parser::lang_el_var),
+            _ => None,
         }
     }
 }
@@ -382,33 +1422,218 @@ impl Parser {
 ///wrapping basic functions for indextree
 impl Parser {
     ///Finds a variable by name if the parser created it already<br/>
+    ///Searches the current scope first, then each outer scope in turn (see [fn_scope_begin](#method.fn_scope_begin)),
+    ///so variables in a scope which has ended are not found<br/>
+    ///The parser is only borrowed, so there is no need to clone it for each lookup, just the element found is cloned<br/>
     ///Option...<br/>
-    ///Some(index of the found variable within the parser.output, and the variable[ParserElement](struct.ParserElement.html)<br/>
+    ///Some(the variable[ParserElement](struct.ParserElement.html)<br/>
     ///None
-    pub fn output_arena_find_element_var(
-        mut self: Parser,
+    pub fn output_arena_find_element_var(self: &Parser, var_name: &str) -> Option<ParserElement> {
+        self.output_arena_find_var_node_id(var_name)
+            .and_then(|node_id| self.output_arena.get(node_id))
+            .map(|node| node.get().clone())
+    }
+
+    ///Whether a variable exists, searching the scopes like [output_arena_find_element_var](#method.output_arena_find_element_var), but without cloning it
+    pub fn has_var(self: &Parser, var_name: &str) -> bool {
+        self.output_arena_find_var_node_id(var_name).is_some()
+    }
+
+    ///As [output_arena_find_element_var](#method.output_arena_find_element_var), but returns the id of the variable's node,
+    ///e.g. to [evaluate](#method.output_arena_evaluate_node) it
+    pub fn output_arena_find_var_node_id(
+        self: &Parser,
         var_name: &str,
-    ) -> Option<ParserElement> {
-        let arena = &mut self.output_arena;
-        //there should only be one or zero
-        let list_of_nodes_with_var_name: Vec<&indextree::Node<ParserElement>> = arena
-            .iter()
-            .filter(|x| x.get().var_name == Some(var_name.to_string()))
-            .collect();
-        if list_of_nodes_with_var_name.len() > 0 {
-            Some(list_of_nodes_with_var_name[0].get().clone())
-        } else {
-            None
+    ) -> Option<indextree::NodeId> {
+        let arena = &self.output_arena;
+        let mut scope_option = Some(self.output_arena_node_parent_id);
+        while let Some(scope_id) = scope_option {
+            //use the most recent if it was reassigned
+            let found_option = scope_id.reverse_children(arena).find(|node_id| {
+                arena
+                    .get(*node_id)
+                    .and_then(|node| node.get().var_name.as_deref())
+                    == Some(var_name)
+            });
+            match found_option {
+                Some(node_id) => return Some(node_id),
+                _ => {
+                    scope_option = arena.get(scope_id).and_then(|node| node.parent());
+                }
+            }
+        }
+        None
+    }
+
+    ///The id of the nth last child of the current parent node, e.g. 0 for the last child
+    pub fn output_arena_get_nth_last_child_id(
+        self: &Parser,
+        index: usize,
+    ) -> Option<indextree::NodeId> {
+        let arena = &self.output_arena;
+        self.output_arena_node_parent_id
+            .reverse_children(arena)
+            .nth(index)
+    }
+
+    ///The type of value a node will have once evaluated, e.g. Int64 for a Sum of two Int64s
+    pub fn output_arena_value_type(
+        self: &Parser,
+        node_id: indextree::NodeId,
+    ) -> Option<ParserElementType> {
+        let arena = &self.output_arena;
+        let el = arena.get(node_id)?.get();
+        match el.el_type {
+            Some(ParserElementType::Sum) | Some(ParserElementType::BinaryOp) => node_id
+                .children(arena)
+                .next()
+                .and_then(|child_id| self.output_arena_value_type(child_id)),
+            _ => el.el_type.clone(),
+        }
+    }
+
+    ///Evaluates a node into a single element with a concrete value, without changing the output_arena<br />
+    /// - a Sum is the Int64 or Float64 of its two children added together<br />
+    /// - a BinaryOp is its two children combined by its reducer, e.g. multiplied together for a "*"<br />
+    /// - a Var (or Print) with a child takes the value of its child<br />
+    /// - anything else is already a value
+    pub fn output_arena_evaluate_node(
+        self: &Parser,
+        node_id: indextree::NodeId,
+    ) -> Result<ParserElement, String> {
+        let arena = &self.output_arena;
+        let el = match arena.get(node_id) {
+            Some(node) => node.get().clone(),
+            _ => return Err("evaluate - can't find the element".to_string()),
+        };
+        let children: Vec<indextree::NodeId> = node_id.children(arena).collect();
+        match (&el.el_type, children.as_slice()) {
+            (Some(ParserElementType::Sum), [value1_id, value2_id])
+            | (Some(ParserElementType::BinaryOp), [value1_id, value2_id]) => {
+                let value1_el = self.output_arena_evaluate_node(*value1_id)?;
+                let value2_el = self.output_arena_evaluate_node(*value2_id)?;
+                let reducer = match el.reducer {
+                    Some(reducer) => reducer,
+                    _ => return Err("evaluate - can't find how to combine the values".to_string()),
+                };
+                match reducer(&value1_el, &value2_el) {
+                    Some(value_el) => Ok(value_el),
+                    None => match (&value1_el.el_type, &value2_el.el_type) {
+                        (Some(ParserElementType::Int64), Some(ParserElementType::Int64)) => {
+                            Err(format!(
+                                "evaluate - {}",
+                                el.reducer_error.as_deref().unwrap_or("integer overflow")
+                            ))
+                        }
+                        (_, _) => Err("evaluate - can't combine these values".to_string()),
+                    },
+                }
+            }
+            (Some(ParserElementType::Sum), _) | (Some(ParserElementType::BinaryOp), _) => {
+                Err("evaluate - a Sum or BinaryOp needs exactly two values".to_string())
+            }
+            (Some(ParserElementType::Var), [value_id])
+            | (Some(ParserElementType::Print), [value_id]) => {
+                let mut value_el = self.output_arena_evaluate_node(*value_id)?;
+                value_el.el_type = el.el_type;
+                value_el.var_name = el.var_name;
+                value_el.var_type = el.var_type;
+                Ok(value_el)
+            }
+            _ => Ok(el),
+        }
+    }
+
+    ///The children of the current parent node, converted to the simpler [ParserEl](struct.ParserEl.html)
+    pub fn output_arena_els(self: &Parser) -> Vec<ParserEl> {
+        self.output_arena_node_parent_id
+            .children(&self.output_arena)
+            .filter_map(|node_id| self.output_arena.get(node_id))
+            .map(|node| ParserEl::from(node.get().clone()))
+            .collect()
+    }
+
+    ///The children of the current parent node, converted into your own type T, e.g. to build a typed AST<br />
+    ///T implements TryFrom&lt;ParserElement&gt;, which can read the element's value by converting it into a [ParserEl](struct.ParserEl.html)<br />
+    ///Fails with the first element that can't be converted
+    pub fn collect_as<T>(self: &Parser) -> Result<Vec<T>, T::Error>
+    where
+        T: TryFrom<ParserElement>,
+    {
+        self.output_arena_node_parent_id
+            .children(&self.output_arena)
+            .filter_map(|node_id| self.output_arena.get(node_id))
+            .map(|node| T::try_from(node.get().clone()))
+            .collect()
+    }
+
+    ///Renders the whole output_arena as a tree for debugging, one element per line, indented by its depth, e.g.<br />
+    ///Root<br />
+    ///&nbsp;&nbsp;Var x I64(1)<br />
+    ///&nbsp;&nbsp;Scope<br />
+    ///&nbsp;&nbsp;&nbsp;&nbsp;Var y I64(2)
+    pub fn debug_tree(self: &Parser) -> String {
+        let arena = &self.output_arena;
+        let mut tree = "".to_string();
+        if let Some(root_id) = self.output_arena_node_parent_id.ancestors(arena).last() {
+            for node_id in root_id.descendants(arena) {
+                let depth = node_id.ancestors(arena).count() - 1;
+                if let Some(node) = arena.get(node_id) {
+                    let el = ParserEl::from(node.get().clone());
+                    tree += "  ".repeat(depth).as_str();
+                    match el.el_type() {
+                        Some(el_type) => tree += format!("{:?}", el_type).as_str(),
+                        _ => tree += "Root",
+                    }
+                    if let Some(var_name) = el.var_name() {
+                        tree += format!(" {}", var_name).as_str();
+                    }
+                    match el.value() {
+                        //a variable without a value just repeats its name
+                        Some(ParserElValue::Var(_)) | None => (),
+                        Some(value) => tree += format!(" {:?}", value).as_str(),
+                    }
+                    tree += "\n";
+                }
+            }
         }
+        tree
     }
 
+    ///Appends the element as the last child of the current parent node, unless build_output is false (see [new_validating](#method.new_validating))
     pub fn output_arena_append_element(mut self: Parser, el: ParserElement) -> Parser {
+        if !self.build_output {
+            return self;
+        }
         let arena = &mut self.output_arena;
         let new_node = arena.new_node(el);
         self.output_arena_node_parent_id.append(new_node, arena);
         self
     }
 
+    ///Removes any children (and their descendants) of the current parent node after the first 'count',<br />
+    ///e.g. to undo the elements appended by a failed parser
+    pub fn output_arena_truncate_children(mut self: Parser, count: usize) -> Parser {
+        let parent_id = self.output_arena_node_parent_id;
+        let children: Vec<indextree::NodeId> =
+            parent_id.children(&self.output_arena).skip(count).collect();
+        self.arenas_before_edit(&children, &[]);
+        arena_truncate_children(&mut self.output_arena, parent_id, count);
+        self
+    }
+
+    ///As [output_arena_truncate_children](#method.output_arena_truncate_children) but for the language_arena
+    pub fn language_arena_truncate_children(mut self: Parser, count: usize) -> Parser {
+        let parent_id = self.language_arena_node_parent_id;
+        let children: Vec<indextree::NodeId> = parent_id
+            .children(&self.language_arena)
+            .skip(count)
+            .collect();
+        self.arenas_before_edit(&[], &children);
+        arena_truncate_children(&mut self.language_arena, parent_id, count);
+        self
+    }
+
     pub fn language_arena_append_functionTypeAndParam(
         mut self: Parser,
         fp: ParserFunctionTypeAndParam,
@@ -421,9 +1646,7 @@ impl Parser {
 
     pub fn test_printing_functionTypeAndParam(mut self: Parser, s: &str) {
         let test_language_arena = &mut self.language_arena;
-        let last_child = self
-            .clone()
-            .language_arena_get_last_child_functionTypeAndParam();
+        let last_child = self.language_arena_get_last_child_functionTypeAndParam();
         println!("####{} {:?}", s, last_child);
     }
 
@@ -438,8 +1661,8 @@ impl Parser {
         //parent_option.get()
     }
 
-    pub fn output_arena_get_last_child_element(mut self: Parser) -> Option<ParserElement> {
-        let arena = &mut self.output_arena;
+    pub fn output_arena_get_last_child_element(self: &Parser) -> Option<ParserElement> {
+        let arena = &self.output_arena;
         let output_arena_current_parent_node_id = self.output_arena_node_parent_id;
         //get parent node
         let parent_option = arena.get(output_arena_current_parent_node_id);
@@ -465,9 +1688,9 @@ impl Parser {
     }
 
     pub fn language_arena_get_last_child_functionTypeAndParam(
-        mut self: Parser,
+        self: &Parser,
     ) -> Option<ParserFunctionTypeAndParam> {
-        let arena = &mut self.language_arena;
+        let arena = &self.language_arena;
         let language_arena_current_parent_node_id = self.language_arena_node_parent_id;
         //get parent node
         let parent_option = arena.get(language_arena_current_parent_node_id);
@@ -493,10 +1716,10 @@ impl Parser {
     }
 
     pub fn output_arena_get_nth_last_child_element(
-        mut self: Parser,
+        self: &Parser,
         index: usize,
     ) -> Option<ParserElement> {
-        let arena = &mut self.output_arena;
+        let arena = &self.output_arena;
         let output_arena_current_parent_node_id = self.output_arena_node_parent_id;
 
         //get node_id
@@ -517,7 +1740,7 @@ impl Parser {
     }
 
     pub fn output_arena_remove_nth_last_child_element(mut self: Parser, index: usize) -> Parser {
-        let arena = &mut self.output_arena;
+        let arena = &self.output_arena;
         let output_arena_current_parent_node_id = self.output_arena_node_parent_id;
 
         //get node_id
@@ -527,13 +1750,84 @@ impl Parser {
         match node_id_option {
             Some(node_id) => {
                 //remove node
-                node_id.remove(arena);
-                self.output_arena = arena.clone();
+                self.arenas_before_edit(&[node_id], &[]);
+                node_id.remove(&mut self.output_arena);
                 self
             }
             _ => self,
         }
     }
+
+    ///Resets the output_arena to just a root node, which becomes the current parent, e.g. to parse the next line of a REPL afresh<br />
+    ///leaves the input_remaining as it is
+    pub fn output_arena_clear(mut self: Parser) -> Parser {
+        let parent_id = self.output_arena_node_parent_id;
+        self.arenas_before_edit(&[parent_id], &[]);
+        let mut output_arena: indextree::Arena<ParserElement> = indextree::Arena::new();
+        self.output_arena_node_parent_id = output_arena.new_node(ParserElement::new());
+        self.output_arena = output_arena;
+        self
+    }
+
+    ///The element of the current parent's child at 'index', counting from the first (in document order), or None if there isn't one
+    pub fn output_arena_get_child_at(self: &Parser, index: usize) -> Option<ParserElement> {
+        let arena = &self.output_arena;
+        self.output_arena_node_parent_id
+            .children(arena)
+            .nth(index)
+            .and_then(|node_id| arena.get(node_id))
+            .map(|node| node.get().clone())
+    }
+
+    ///Removes the current parent's child at 'index' (and its descendants), counting from the first (in document order)<br />
+    ///does nothing if there isn't one
+    pub fn output_arena_remove_child_at(mut self: Parser, index: usize) -> Parser {
+        let child_option = self
+            .output_arena_node_parent_id
+            .children(&self.output_arena)
+            .nth(index);
+        if let Some(child) = child_option {
+            self.arenas_before_edit(&[child], &[]);
+            let arena = &mut self.output_arena;
+            //remove the deepest nodes first, so none are re-attached to the parent
+            let descendants: Vec<indextree::NodeId> = child.descendants(arena).collect();
+            for node_id in descendants.into_iter().rev() {
+                node_id.remove(arena);
+            }
+        }
+        self
+    }
+
+    ///Replaces the current parent's last child element with the result of 'f', e.g. to negate a number just parsed<br />
+    ///the node is changed in place, rather than removed and appended again, so it keeps its position and any children
+    pub fn map_last_element<F>(mut self: Parser, f: F) -> Parser
+    where
+        F: Fn(ParserElement) -> ParserElement,
+    {
+        let last_child_id_option = self
+            .output_arena
+            .get(self.output_arena_node_parent_id)
+            .and_then(|node| node.last_child());
+        if let Some(last_child_id) = last_child_id_option {
+            self.arenas_before_edit(&[last_child_id], &[]);
+        }
+        let last_child_option =
+            last_child_id_option.and_then(|node_id| self.output_arena.get_mut(node_id));
+        if let Some(node) = last_child_option {
+            let el = node.get().clone();
+            *node.get_mut() = f(el);
+        }
+        self
+    }
+
+    ///Changes the type of the current parent's last child element, e.g. to turn a value just parsed into a Var, like [fn_var_assign](#method.fn_var_assign) does<br />
+    ///its value is kept, and it does nothing if there is no last child
+    pub fn set_last_element_type(self: Parser, el_type: ParserElementType) -> Parser {
+        self.map_last_element(|mut el| {
+            el.el_type = Some(el_type.clone());
+            el
+        })
+    }
 }
 /// ## Language Aliases
 ///Functions to help decode a string of aliases of the parser functions of this module
@@ -541,8 +1835,17 @@ impl Parser {
     pub fn lang_one_of_all_lang_parsers(self: Parser) -> Parser {
         self.combi_first_success_of(
             &[
+                //custom parsers are tried first, so they can reuse a built-in alias name
+                Parser::lang_custom_parser,
                 //combinators
-                //Parser::lang_combi_one_or_more,
+                Parser::lang_combi_one_or_more,
+                Parser::lang_combi_optional,
+                Parser::lang_combi_first_success_of,
+                //elements
+                Parser::lang_el_str,
+                Parser::lang_el_int,
+                Parser::lang_el_float,
+                Parser::lang_el_var,
                 //primitives
                 Parser::lang_prim_word,
                 Parser::lang_prim_eols_or_eof,
@@ -569,7 +1872,7 @@ impl Parser {
                 self = self.language_arena_append_functionTypeAndParam(pf);
                 self
             } else {
-                self.display_error(error_text);
+                self.record_error(error_text);
                 self
             }
         } else {
@@ -577,6 +1880,43 @@ impl Parser {
         }
     }
 
+    ///Matches the name of one of the custom_parsers, passed to [new_and_parse_aliases_with_custom_parsers](#method.new_and_parse_aliases_with_custom_parsers)
+    /// - the longest name is tried first, so "ab" is matched before "a"
+    pub fn lang_custom_parser(mut self: Parser) -> Parser {
+        if self.success {
+            let mut names: Vec<(String, ParserFunction)> = self
+                .custom_parsers
+                .iter()
+                .map(|(name, f)| (name.clone(), *f))
+                .collect();
+            names.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then(a.cmp(b)));
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            for (name, f) in names {
+                let result = Parser::lang_factory_takes_parser(
+                    self.clone(),
+                    name.as_str(),
+                    (
+                        ParserFunctionType::TakesParser(f),
+                        ParserFunctionParam::None,
+                    ),
+                    "lang_custom_parser",
+                );
+                if result.success {
+                    let mut result = result;
+                    result.display_errors = display_errors_previous_flag_setting;
+                    return result;
+                }
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            self.success = false;
+            self.record_error("lang_custom_parser");
+            self
+        } else {
+            self
+        }
+    }
+
     //Primitives
 
     pub fn lang_prim_next(self: Parser) -> Parser {
@@ -674,13 +2014,13 @@ impl Parser {
             if self.success {
                 let fp = (
                     ParserFunctionType::TakesParserWord(Parser::prim_word),
-                    ParserFunctionParam::String(self.clone().chomp),
+                    ParserFunctionParam::String(self.chomp.clone()),
                 );
                 self = self.language_arena_append_functionTypeAndParam(fp);
                 self = self.chomp_clear();
                 self
             } else {
-                self.display_error("lang_prim_word");
+                self.record_error("lang_prim_word");
                 self
             }
         } else {
@@ -688,38 +2028,175 @@ impl Parser {
         }
     }
 
+    //Elements
+
+    pub fn lang_el_str(self: Parser) -> Parser {
+        Parser::lang_factory_takes_parser(
+            self,
+            "$str",
+            (
+                ParserFunctionType::TakesParser(Parser::el_str),
+                ParserFunctionParam::None,
+            ),
+            "lang_el_str",
+        )
+    }
+
+    pub fn lang_el_int(self: Parser) -> Parser {
+        Parser::lang_factory_takes_parser(
+            self,
+            "$int",
+            (
+                ParserFunctionType::TakesParser(Parser::el_int),
+                ParserFunctionParam::None,
+            ),
+            "lang_el_int",
+        )
+    }
+
+    pub fn lang_el_float(self: Parser) -> Parser {
+        Parser::lang_factory_takes_parser(
+            self,
+            "$flt",
+            (
+                ParserFunctionType::TakesParser(Parser::el_float),
+                ParserFunctionParam::None,
+            ),
+            "lang_el_float",
+        )
+    }
+
+    pub fn lang_el_var(self: Parser) -> Parser {
+        Parser::lang_factory_takes_parser(
+            self,
+            "$var",
+            (
+                ParserFunctionType::TakesParser(Parser::el_var),
+                ParserFunctionParam::None,
+            ),
+            "lang_el_var",
+        )
+    }
+
     //Combinators
 
-    pub fn lang_combi_one_or_more(mut self: Parser) -> Parser {
+    ///"1+" followed by a primitive alias, e.g. "1+#" for [combi_one_or_more_of](#method.combi_one_or_more_of) [prim_digit](#method.prim_digit)
+    pub fn lang_combi_one_or_more(self: Parser) -> Parser {
+        Parser::lang_factory_takes_parser_fn(
+            self,
+            "1+",
+            Parser::combi_one_or_more_of,
+            "lang_combi_one_or_more",
+        )
+    }
+
+    ///"??" followed by a primitive alias, e.g. "??#" for [combi_optional](#method.combi_optional) [prim_digit](#method.prim_digit)
+    pub fn lang_combi_optional(self: Parser) -> Parser {
+        Parser::lang_factory_takes_parser_fn(
+            self,
+            "??",
+            Parser::combi_optional,
+            "lang_combi_optional",
+        )
+    }
+
+    ///Matches the 'word' of a combinator alias followed by a primitive alias,
+    ///then appends the combinator with that primitive as its ParserFn param
+    pub fn lang_factory_takes_parser_fn(
+        mut self: Parser,
+        word: &str,
+        combinator: ParserFunctionParserFunction,
+        error_text: &str,
+    ) -> Parser {
         if self.success {
+            let mut original_self = self.clone();
             let display_errors_previous_flag_setting = self.display_errors;
             self.display_errors = false;
             self = self
-                .prim_word("1+")
+                .prim_word(word)
                 .combi_zero_or_more_of(Parser::prim_space)
-                .combi_until_first_do_second([Parser::prim_space, Parser::prim_next].to_vec());
+                .chomp_clear()
+                .lang_one_of_all_lang_parsers();
             self.display_errors = display_errors_previous_flag_setting;
-            if self.success {
-                //TODO can't just do this because the chomp string, might be another nested combi...
-                //ParserFunctionParam::ParserFn(Parser::get_parser_function_by_name(
-                //    self.clone().chomp,
-                //)),
-
-                //Have to call the parser on that string
-                //But it's getting a bit manual - need to look at a more nested AST approach?
-
-                //TODO replace with output_arena_append_element
-                /*self.language_arena.push((
-                    ParserFunctionType::TakesParserFn(Parser::combi_one_or_more_of),
-                    ParserFunctionParam::ParserFn(Parser::get_parser_function_by_name(
-                        self.clone().chomp,
-                    )),
-                ));*/
-                self = self.chomp_clear();
-                self
+            //the inner parser has been appended to the language_arena, so take it back off to wrap it in the combinator
+            let inner_option = if self.success {
+                self.language_arena_get_last_child_functionTypeAndParam()
             } else {
-                self.display_error("lang_combi_one_or_more");
+                None
+            };
+            match inner_option {
+                Some((ParserFunctionType::TakesParser(inner), _)) => {
+                    let children = self
+                        .language_arena_node_parent_id
+                        .children(&self.language_arena)
+                        .count();
+                    let fp = (
+                        ParserFunctionType::TakesParserFn(combinator),
+                        ParserFunctionParam::ParserFn(inner),
+                    );
+                    self = self
+                        .language_arena_truncate_children(children - 1)
+                        .language_arena_append_functionTypeAndParam(fp);
+                    self = self.chomp_clear();
+                    self
+                }
+                //TODO handle nested combinators, or prim_word, which aren't just a ParserFunction
+                _ => {
+                    original_self.success = false;
+                    original_self.record_error(error_text);
+                    original_self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    ///"[", a space separated list of primitive aliases, then "]", e.g. "[# @]" for [combi_first_success_of](#method.combi_first_success_of) [prim_digit](#method.prim_digit) or [prim_char](#method.prim_char)
+    pub fn lang_combi_first_success_of(mut self: Parser) -> Parser {
+        if self.success {
+            let mut original_self = self.clone();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.prim_word("[").chomp_clear();
+            let mut inners: Vec<ParserFunction> = vec![];
+            while self.success {
+                self = self.combi_zero_or_more_of(Parser::prim_space);
+                if self.input_remaining().starts_with(']') {
+                    self = self.prim_word("]").chomp_clear();
+                    break;
+                }
+                //each inner parser is appended to the language_arena, so take it back off to add it to the list
+                self = self.lang_one_of_all_lang_parsers();
+                let inner_option = if self.success {
+                    self.language_arena_get_last_child_functionTypeAndParam()
+                } else {
+                    None
+                };
+                match inner_option {
+                    Some((ParserFunctionType::TakesParser(inner), _)) => {
+                        inners.push(inner);
+                        let children = self
+                            .language_arena_node_parent_id
+                            .children(&self.language_arena)
+                            .count();
+                        self = self.language_arena_truncate_children(children - 1);
+                    }
+                    _ => self.success = false,
+                }
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success && !inners.is_empty() {
+                let fp = (
+                    ParserFunctionType::TakesParserVecFn(Parser::combi_first_success_of),
+                    ParserFunctionParam::VecParserFn(inners),
+                );
+                self = self.language_arena_append_functionTypeAndParam(fp);
                 self
+            } else {
+                original_self.success = false;
+                original_self.record_error("lang_combi_first_success_of");
+                original_self
             }
         } else {
             self
@@ -733,22 +2210,26 @@ impl Parser {
     ///Matches whatever the next character is, fails if eof
     pub fn prim_next(mut self: Parser) -> Parser {
         if self.success {
+            let error_previous = self.error.clone();
+            let error_position_previous = self.error_position;
             self = self.prim_eof();
             if self.success {
                 self.success = false;
                 self
             } else {
-                match self.clone().input_remaining.graphemes(true).next() {
+                self.error = error_previous;
+                self.error_position = error_position_previous;
+                match self.input_original[self.position..].graphemes(true).next() {
                     Some(next) => {
-                        self.input_remaining = self.input_remaining[next.len()..].to_string();
+                        self.position += next.len();
                         if self.chomping {
-                            self.chomp += next;
+                            self.chomp.push_str(next);
                         };
                         self.success = true;
                         self
                     }
                     _ => {
-                        self.display_error("prim_next");
+                        self.record_error("prim_next");
                         self.success = false;
                         self
                     }
@@ -759,14 +2240,107 @@ impl Parser {
         }
     }
 
-    pub fn prim_space(mut self: Parser) -> Parser {
-        let chomping_previous_flag_setting = self.chomping;
+    ///Matches exactly 'n' graphemes whatever they are, e.g. for fixed width records, fails if fewer than 'n' remain
+    pub fn prim_take(mut self: Parser, n: usize) -> Parser {
+        if self.success {
+            let mut length = 0;
+            let mut count = 0;
+            for next in self.input_remaining().graphemes(true).take(n) {
+                length += next.len();
+                count += 1;
+            }
+            if count == n {
+                if self.chomping {
+                    let next = &self.input_original[self.position..self.position + length];
+                    self.chomp.push_str(next);
+                };
+                self.position += length;
+                self
+            } else {
+                self.success = false;
+                self.record_error("prim_take");
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///Matches with [prim_next](#method.prim_next) until the chomp is 'target' graphemes long, e.g. for fixed width fields after a prefix<br />
+    ///Unlike [prim_take](#method.prim_take) it counts the whole chomp so far, not just what it matches itself, so it does nothing if the chomp is already long enough<br />
+    ///Fails if the input runs out first, leaving the parser as it was
+    pub fn prim_chomp_until_len(mut self: Parser, target: usize) -> Parser {
+        if self.success {
+            let snap = self.snapshot();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            let mut length = self.chomp.graphemes(true).count();
+            while self.success && length < target {
+                self = self.prim_next();
+                length += 1;
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                self
+            } else {
+                self = self.restore(snap);
+                self.success = false;
+                self.record_error("prim_chomp_until_len");
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///Matches the rest of the current line, up to but not including its \r\n, \n or \r, which is left for a following [prim_eol](#method.prim_eol)<br />
+    ///Beware, it will always succeed! Even on an empty line, or at the end of the input
+    pub fn prim_line(mut self: Parser) -> Parser {
+        if self.success {
+            let remaining = self.input_remaining();
+            let length = remaining.find(['\r', '\n']).unwrap_or(remaining.len());
+            if self.chomping {
+                let line = &self.input_original[self.position..self.position + length];
+                self.chomp.push_str(line);
+            };
+            self.position += length;
+            self
+        } else {
+            self
+        }
+    }
+
+    ///Matches all of the remaining input in one go, e.g. to read everything to the end<br />
+    ///Beware, it will always succeed! Even at the end of the input, unlike [prim_next](#method.prim_next)
+    pub fn prim_rest(mut self: Parser) -> Parser {
+        if self.success {
+            if self.chomping {
+                let rest = &self.input_original[self.position..];
+                self.chomp.push_str(rest);
+            };
+            self.position = self.input_original.len();
+            self
+        } else {
+            self
+        }
+    }
+
+    pub fn prim_space(mut self: Parser) -> Parser {
+        let chomping_previous_flag_setting = self.chomping;
         self.chomping = false;
         self = self.prim_word(" ");
         self.chomping = chomping_previous_flag_setting;
         self
     }
 
+    pub fn prim_tab(mut self: Parser) -> Parser {
+        let chomping_previous_flag_setting = self.chomping;
+        self.chomping = false;
+        self = self.prim_word("\t");
+        self.chomping = chomping_previous_flag_setting;
+        self
+    }
+
     pub fn prim_quote(mut self: Parser) -> Parser {
         let chomping_previous_flag_setting = self.chomping;
         self.chomping = false;
@@ -784,20 +2358,209 @@ impl Parser {
     }
 
     /// Matches any series of [prim_car](#method.prim_char) in the supplied 'expected' string
-    /// Always succeeds
-    pub fn prim_word(mut self: Parser, expected: &str) -> Parser {
+    /// Always succeeds<br />
+    /// Compared with strip_prefix, so it never slices the input in the middle of a multi-byte character, e.g. "ab" just fails on "aé"
+    pub fn prim_word(self: Parser, expected: &str) -> Parser {
+        self.and_then(|mut s: Parser| {
+            if s.input_original[s.position..]
+                .strip_prefix(expected)
+                .is_some()
+            {
+                s.position += expected.len();
+                if s.chomping {
+                    s.chomp.push_str(expected);
+                };
+                s.success = true;
+                s
+            } else {
+                s.success = false;
+                s
+            }
+        })
+    }
+
+    /// Matches the first of the supplied 'words' found at the current position, e.g. a keyword<br />
+    /// Beware, order matters! The words are tried in the given order, so put longer words first, e.g. ["int", "in"], otherwise "in" would match the start of "int"
+    pub fn prim_word_oneof(mut self: Parser, words: &[&str]) -> Parser {
         if self.success {
-            match self.clone().input_remaining.get(0..expected.len()) {
-                Some(next) if next == expected => {
-                    self.input_remaining = self.input_remaining[expected.len()..].to_string();
+            match words
+                .iter()
+                .find(|word| self.input_remaining().starts_with(**word))
+            {
+                Some(word) => self.prim_word(word),
+                _ => {
+                    self.success = false;
+                    self.record_error("prim_word_oneof");
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Matches the supplied 'word' only at a word boundary, i.e. followed by whitespace, punctuation or the end of the input<br />
+    /// e.g. "in" matches "in x" but not the start of "int" or "in_x", unlike [prim_word](#method.prim_word)
+    pub fn prim_keyword(mut self: Parser, word: &str) -> Parser {
+        if self.success {
+            let next_char = self
+                .input_remaining()
+                .strip_prefix(word)
+                .map(|rest| rest.chars().next());
+            match next_char {
+                Some(None) => self.prim_word(word),
+                Some(Some(c)) if !(c.is_alphanumeric() || c == '_') => self.prim_word(word),
+                _ => {
+                    self.success = false;
+                    self.record_error("prim_keyword");
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Matches the supplied 'expected' string, but with the first character in either case, e.g. "print" matches "Print" and "print", but not "PRINT"<br />
+    /// The original input is chomped, e.g. "Print"
+    pub fn prim_word_cap_insensitive(mut self: Parser, expected: &str) -> Parser {
+        if self.success {
+            let mut expected_chars = expected.chars();
+            let mut input_chars = self.input_remaining().chars();
+            let matched_length = match (expected_chars.next(), input_chars.next()) {
+                (Some(expected_first), Some(input_first))
+                    if expected_first.to_lowercase().eq(input_first.to_lowercase())
+                        && input_chars.as_str().starts_with(expected_chars.as_str()) =>
+                {
+                    Some(input_first.len_utf8() + expected_chars.as_str().len())
+                }
+                (None, _) => Some(0),
+                (_, _) => None,
+            };
+            match matched_length {
+                Some(length) => {
+                    let next = &self.input_original[self.position..self.position + length];
+                    if self.chomping {
+                        self.chomp.push_str(next);
+                    };
+                    self.position += length;
+                    self
+                }
+                None => {
+                    self.success = false;
+                    self.record_error("prim_word_cap_insensitive");
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Matches the supplied 'expected' string ignoring accents, e.g. "cafe" matches "café"<br />
+    /// Both are compared after unicode NFD normalization with the combining marks removed,
+    /// and the original input is chomped, not the folded version
+    pub fn prim_word_fold(mut self: Parser, expected: &str) -> Parser {
+        if self.success {
+            let fold = |s: &str| -> String { s.nfd().filter(|c| !is_combining_mark(*c)).collect() };
+            let expected_folded = fold(expected);
+            let mut input_folded = "".to_string();
+            let mut matched_length = None;
+            let mut length = 0;
+            for next in self.input_remaining().graphemes(true) {
+                if input_folded == expected_folded {
+                    matched_length = Some(length);
+                    break;
+                }
+                input_folded.push_str(fold(next).as_str());
+                length += next.len();
+                if !expected_folded.starts_with(input_folded.as_str()) {
+                    break;
+                }
+            }
+            if input_folded == expected_folded {
+                matched_length = Some(length);
+            }
+            match matched_length {
+                Some(length) => {
+                    let next = &self.input_original[self.position..self.position + length];
+                    if self.chomping {
+                        self.chomp.push_str(next);
+                    };
+                    self.position += length;
+                    self
+                }
+                None => {
+                    self.success = false;
+                    self.record_error("prim_word_fold");
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Matches an optional leading sign, i.e. "+" or "-", for numbers like [el_int](#method.el_int) and [el_float](#method.el_float)<br />
+    /// Beware, it will always succeed!
+    pub fn prim_sign(self: Parser) -> Parser {
+        self.combi_optional(|s: Parser| s.prim_word_oneof(&["+", "-"]))
+    }
+
+    /// Matches the supplied 'expected' string case-insensitively, e.g. "true" matches "TRUE" or "tRuE"<br />
+    /// Compares one char at a time (using unicode lowercasing) so the input can be a different byte length to 'expected',
+    /// and chomps the original input, not the lowercased version
+    pub fn prim_word_ci(mut self: Parser, expected: &str) -> Parser {
+        if self.success {
+            let mut input_chars = self.input_remaining().char_indices();
+            let mut matched_length = Some(0);
+            for expected_char in expected.chars() {
+                match input_chars.next() {
+                    Some((i, next)) if next.to_lowercase().eq(expected_char.to_lowercase()) => {
+                        matched_length = Some(i + next.len_utf8());
+                    }
+                    _ => {
+                        matched_length = None;
+                        break;
+                    }
+                }
+            }
+            match matched_length {
+                Some(length) => {
+                    let next = &self.input_original[self.position..self.position + length];
+                    self.position += length;
                     if self.chomping {
-                        self.chomp += next;
+                        self.chomp.push_str(next);
                     };
                     self.success = true;
                     self
                 }
                 _ => {
                     self.success = false;
+                    self.record_error("prim_word_ci");
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Matches exactly one grapheme cluster equal to 'expected', e.g. a flag or an emoji with a skin tone, which are made of several chars<br />
+    /// Unlike [prim_word](#method.prim_word) it won't match just the start of a longer cluster, e.g. "👍" doesn't match "👍🏽"
+    pub fn prim_grapheme(mut self: Parser, expected: &str) -> Parser {
+        if self.success {
+            match self.input_original[self.position..].graphemes(true).next() {
+                Some(next) if next == expected => {
+                    self.position += next.len();
+                    if self.chomping {
+                        self.chomp.push_str(next);
+                    };
+                    self
+                }
+                _ => {
+                    self.success = false;
+                    self.record_error("prim_grapheme");
                     self
                 }
             }
@@ -806,19 +2569,21 @@ impl Parser {
         }
     }
 
-    /// Matches any unicode character except whitespace '&nbsp;'
+    /// Matches any unicode character except whitespace '&nbsp;'<br />
+    /// Despite its name it doesn't match a space, so prefer the clearer [prim_non_space_char](#method.prim_non_space_char),
+    /// or use [prim_next](#method.prim_next) to match any character including a space
     pub fn prim_char(mut self: Parser) -> Parser {
         if self.success {
-            match self.clone().input_remaining.graphemes(true).next() {
+            match self.input_original[self.position..].graphemes(true).next() {
                 Some(next) => {
                     if next == " " {
-                        self.display_error("prim_char");
+                        self.record_error("prim_char");
                         self.success = false;
                         self
                     } else {
-                        self.input_remaining = self.input_remaining[next.len()..].to_string();
+                        self.position += next.len();
                         if self.chomping {
-                            self.chomp += next;
+                            self.chomp.push_str(next);
                         };
                         self.success = true;
                         self
@@ -826,7 +2591,7 @@ impl Parser {
                 }
                 _ => {
                     self.success = false;
-                    self.display_error("prim_char");
+                    self.record_error("prim_char");
                     self
                 }
             }
@@ -836,218 +2601,228 @@ impl Parser {
         }
     }
 
-    /// Matches a single digit 0,1,2,3,4,5,6,7,8,9
-    pub fn prim_digit(mut self: Parser) -> Parser {
+    /// Matches everything up to (but not including) the next 'delim' character, e.g. "name" from "name|value" with '|'<br />
+    /// Succeeds even if 'delim' is next, but fails if eof is reached without finding 'delim'
+    pub fn prim_until_char(mut self: Parser, delim: char) -> Parser {
         if self.success {
-            match self.input_remaining.chars().next() {
-                Some(next) if next.is_digit(10) => {
-                    self.input_remaining = self.input_remaining[next.len_utf8()..].to_string();
+            match self.input_remaining().find(delim) {
+                Some(length) => {
+                    let next = &self.input_original[self.position..self.position + length];
+                    self.position += length;
                     if self.chomping {
-                        self.chomp += next.encode_utf8(&mut [0; 1]);
+                        self.chomp.push_str(next);
                     };
-
-                    self.success = true;
                     self
                 }
                 _ => {
                     self.success = false;
-                    self.display_error("prim_digit");
+                    self.record_error("prim_until_char");
                     self
                 }
             }
         } else {
-            self.display_error("prim_digit");
             self
         }
     }
 
-    /// Matches [a combination of one or more of](#method.combi_one_or_more_of) a single \r\n or \n
-    pub fn prim_eols(mut self: Parser) -> Parser {
+    /// Matches any unicode character except a space, exactly like [prim_char](#method.prim_char), but clearly named
+    pub fn prim_non_space_char(self: Parser) -> Parser {
+        self.prim_char()
+    }
+
+    /// Matches the next character if 'pred' returns true for it, e.g. `prim_char_if(|c| c.is_ascii_punctuation())`<br />
+    /// Fails if the next grapheme is more than one char (e.g. "e" with a combining accent), so 'pred' only ever sees a whole character
+    pub fn prim_char_if<P>(mut self: Parser, pred: P) -> Parser
+    where
+        P: Fn(char) -> bool,
+    {
         if self.success {
-            let newline1 = self
-                .clone()
-                .combi_one_or_more_of(|s| Parser::prim_word(s, "\r\n"));
-            let newline2 = self
-                .clone()
-                .combi_one_or_more_of(|s| Parser::prim_word(s, "\n"));
-            if newline1.success {
-                newline1
-            } else if newline2.success {
-                newline2
-            } else {
-                self.success = false;
-                self.display_error("prim_eols");
-                self
+            let next_option = self.input_original[self.position..].graphemes(true).next();
+            let matched_option = next_option.filter(|next| {
+                let mut chars = next.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => pred(c),
+                    _ => false,
+                }
+            });
+            match matched_option {
+                Some(next) => {
+                    self.position += next.len();
+                    if self.chomping {
+                        self.chomp.push_str(next);
+                    };
+                    self
+                }
+                _ => {
+                    self.success = false;
+                    self.record_error("prim_char_if");
+                    self
+                }
             }
         } else {
-            self.display_error("prim_eols");
+            self.display_error("prim_char_if");
             self
         }
     }
 
-    ///Matches if you've reached the end of the parsed string, i.e. check for an empty string at this stage of the parser...
-    pub fn prim_eof(mut self: Parser) -> Parser {
-        if self.success && self.input_remaining.len() == 0 {
+    /// Matches 'n' of the character 'c' in a row, e.g. "---" with ('-', 3)<br />
+    /// It doesn't mind if there are more afterwards, so "----" also matches, leaving "-".
+    /// Use [prim_repeat_char_exactly](#method.prim_repeat_char_exactly) to fail if there are more
+    pub fn prim_repeat_char(mut self: Parser, c: char, n: usize) -> Parser {
+        if self.success {
+            let expected = c.to_string().repeat(n);
+            self = self.prim_word(expected.as_str());
+            if !self.success {
+                self.record_error("prim_repeat_char");
+            }
             self
         } else {
-            self.success = false;
-            self.display_error("prim_eof");
             self
         }
     }
 
-    ///Matches either (prim_eolss)[#method.prim_eolss] or (prim_eof)[#method.prim_eof]
-    pub fn prim_eols_or_eof(mut self: Parser) -> Parser {
+    /// Matches exactly 'n' of the character 'c' in a row, like [prim_repeat_char](#method.prim_repeat_char),
+    /// but fails if they are followed by another 'c', e.g. "----" doesn't match ('-', 3)
+    pub fn prim_repeat_char_exactly(mut self: Parser, c: char, n: usize) -> Parser {
         if self.success {
-            let display_errors_previous_flag_setting = self.display_errors;
-            self.display_errors = false;
-            self = self.combi_first_success_of(&[Parser::prim_eols, Parser::prim_eof].to_vec());
+            let snap = self.snapshot();
+            self = self.prim_repeat_char(c, n);
+            if self.success && self.input_remaining().starts_with(c) {
+                self.success = false;
+            }
             if self.success {
-                self.display_errors = display_errors_previous_flag_setting;
                 self
             } else {
-                self.display_error("prim_eols_or_eof");
-                self.display_errors = display_errors_previous_flag_setting;
+                self = self.restore(snap);
+                self.success = false;
+                self.record_error("prim_repeat_char_exactly");
                 self
             }
         } else {
             self
         }
     }
-}
-/// ## Parser combinators
-/// they will (TODO) Panic at an error -  used to combine multiple [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators)
-impl Parser {
-    ///Matches either one, or multiple of any one parser or combinator of parsers
-    pub fn combi_one_or_more_of<F>(mut self: Parser, func: F) -> Parser
-    where
-        F: Fn(Parser) -> Parser,
-    {
+
+    /// Matches a single character of the [CharClass](enum.CharClass.html), e.g. CharClass::Upper for "A" but not "a"<br />
+    /// Like [prim_char_if](#method.prim_char_if) it only matches a grapheme which is a single character, so not e.g. "e\u{301}"
+    pub fn prim_class(mut self: Parser, class: CharClass) -> Parser {
         if self.success {
-            let chomp = self.clone().chomp;
             let display_errors_previous_flag_setting = self.display_errors;
             self.display_errors = false;
-            while self.success {
-                self = func(self);
-            }
+            self = self.prim_char_if(|c| class.matches(c));
             self.display_errors = display_errors_previous_flag_setting;
-            if self.chomp == chomp {
-                self.display_error("combi_one_or_more_of");
-                self.success = false;
-                self
-            } else {
-                self.success = true;
-                self
+            if !self.success {
+                self.record_error("prim_class");
             }
+            self
         } else {
             self
         }
     }
-    ///Matches either zero, one or multiple of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators).<br />
-    ///Beware, it will always succeed!
-    pub fn combi_zero_or_more_of<F>(mut self: Parser, func: F) -> Parser
-    where
-        F: Fn(Parser) -> Parser,
-    {
+
+    /// Matches a single digit 0,1,2,3,4,5,6,7,8,9
+    pub fn prim_digit(mut self: Parser) -> Parser {
         if self.success {
-            let display_errors_previous_flag_setting = self.display_errors;
-            self.display_errors = false;
-            while self.success {
-                self = func(self)
+            match self.input_remaining().chars().next() {
+                Some(next) if next.is_digit(10) => {
+                    self.position += next.len_utf8();
+                    if self.chomping {
+                        self.chomp.push(next);
+                    };
+
+                    self.success = true;
+                    self
+                }
+                _ => {
+                    self.success = false;
+                    self.record_error("prim_digit");
+                    self
+                }
             }
-            self.display_errors = display_errors_previous_flag_setting;
-            self.success = true;
-            self
         } else {
+            self.display_error("prim_digit");
             self
         }
     }
 
-    ///Matches either zero, one or multiple of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators)<br />
-    ///until it reaches the second supplied [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators)
-    pub fn combi_until_first_do_second<F>(mut self: Parser, first_and_second: Vec<F>) -> Parser
-    where
-        F: Fn(Parser) -> Parser,
-    {
+    /// Matches [a combination of one or more of](#method.combi_one_or_more_of) [prim_eol](#method.prim_eol), i.e. \r\n, \n or \r
+    pub fn prim_eols(mut self: Parser) -> Parser {
         if self.success {
             let display_errors_previous_flag_setting = self.display_errors;
             self.display_errors = false;
-            while self.success {
-                self = Parser::combi_first_success_of(self, &first_and_second);
-            }
+            //a failed prim_eol doesn't consume anything, so there is nothing to undo if none match
+            self = self.combi_one_or_more_of(Parser::prim_eol);
             self.display_errors = display_errors_previous_flag_setting;
-            self.success = true;
-            self
+            if self.success {
+                self
+            } else {
+                self.record_error("prim_eols");
+                self
+            }
         } else {
+            self.display_error("prim_eols");
             self
         }
     }
 
-    ///Matches either one or zero of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators).<br />
-    ///Beware, it will always succeed!
-    pub fn combi_optional<F>(mut self: Parser, func: F) -> Parser
-    where
-        F: Fn(Parser) -> Parser,
-    {
+    /// Matches exactly one \r\n, \n or (old Mac style) \r, preferring \r\n<br />
+    /// Unlike [prim_eols](#method.prim_eols) it won't swallow blank lines
+    pub fn prim_eol(mut self: Parser) -> Parser {
         if self.success {
-            self = func(self);
-            self.success = true;
-            self
+            let eol_option = ["\r\n", "\n", "\r"]
+                .iter()
+                .find(|eol| self.input_remaining().starts_with(**eol));
+            match eol_option {
+                Some(eol) => self.prim_word(eol),
+                None => {
+                    self.success = false;
+                    self.record_error("prim_eol");
+                    self
+                }
+            }
         } else {
-            self.display_error("combi_optional");
+            self.display_error("prim_eol");
             self
         }
     }
 
-    ///Tries to match one of the parsers supplied in an array (vec) of [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators).
-    ///
-    ///It matches in the order supplied
-    pub fn combi_first_success_of<F>(mut self: Parser, funcs: &Vec<F>) -> Parser
-    where
-        F: Fn(Parser) -> Parser,
-    {
+    ///Matches either a single [prim_eol](#method.prim_eol) (\r\n, \n or \r) or [prim_eof](#method.prim_eof)
+    pub fn prim_any_newline_or_eof(mut self: Parser) -> Parser {
         if self.success {
-            for func in funcs {
-                let mut new_self = self.clone();
-                let display_errors_previous_flag_setting = self.display_errors;
-                new_self.display_errors = false;
-                new_self = func(new_self);
-                new_self.display_errors = display_errors_previous_flag_setting;
-                if new_self.success {
-                    return new_self;
-                }
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.combi_first_success_of(&[Parser::prim_eol, Parser::prim_eof].to_vec());
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                self
+            } else {
+                self.record_error("prim_any_newline_or_eof");
+                self
             }
-            self.display_error("combi_first_success_of");
-            self.success = false;
-            return self;
         } else {
-            return self;
-        };
+            self
+        }
     }
-}
 
-/// ## Parser Elements
-
-impl Parser {
-    ///string, e.g. "123" or "The quick brown fox jumps over the lazy dog"
-    pub fn el_str(mut self: Parser) -> Parser {
+    ///Matches only if the current line is indented by exactly 'spaces' spaces from the start of the line, e.g. "    x" matches prim_indent(4)<br />
+    ///Consumes the indentation (if it hasn't been already), without chomping it.<br />
+    ///Use with [indent_current](#method.indent_current) to match the level on the indents stack
+    pub fn prim_indent(mut self: Parser, spaces: usize) -> Parser {
         if self.success {
-            let display_errors_previous_flag_setting = self.display_errors;
-            self.display_errors = false;
-            self = self
-                .prim_quote()
-                .combi_until_first_do_second([Parser::prim_quote, Parser::prim_next].to_vec());
-            self.display_errors = display_errors_previous_flag_setting;
-            if self.success {
-                let mut el = ParserElement::new();
-                let val = self.clone().chomp;
-                el.el_type = Some(ParserElementType::Str);
-                el.string = Some(val);
-                self = self.output_arena_append_element(el);
-                self = self.chomp_clear();
+            let line_start = self.input_original[..self.position]
+                .rfind(['\n', '\r'])
+                .map_or(0, |i| i + 1);
+            let indent = self.input_original[line_start..]
+                .chars()
+                .take_while(|c| *c == ' ')
+                .count();
+            //still within the indentation, and it is the right size
+            if self.position <= line_start + indent && indent == spaces {
+                self.position = line_start + indent;
                 self
             } else {
-                self.display_error("el_str");
+                self.success = false;
+                self.record_error("prim_indent");
                 self
             }
         } else {
@@ -1055,25 +2830,23 @@ impl Parser {
         }
     }
 
-    ///integer number, e.g. 12 or -123456
-    pub fn el_int(mut self: Parser) -> Parser {
+    ///Matches a comment, i.e. "//" followed by anything up to (but not including) the end of the line, or eof<br />
+    ///Comments are discarded, so nothing is chomped
+    pub fn prim_comment(mut self: Parser) -> Parser {
         if self.success {
-            let display_errors_previous_flag_setting = self.display_errors;
-            self.display_errors = false;
-            self = self
-                .combi_optional(|s: Parser| Parser::prim_word(s, "-"))
-                .combi_one_or_more_of(Parser::prim_digit);
-            self.display_errors = display_errors_previous_flag_setting;
+            let chomping_previous_flag_setting = self.chomping;
+            self.chomping = false;
+            self = self.prim_word("//");
+            self.chomping = chomping_previous_flag_setting;
             if self.success {
-                let mut el = ParserElement::new();
-                let val = self.clone().chomp.parse().unwrap();
-                el.el_type = Some(ParserElementType::Int64);
-                el.int64 = Some(val);
-                self = self.output_arena_append_element(el);
-                self = self.chomp_clear();
+                let length = self
+                    .input_remaining()
+                    .find(['\r', '\n'])
+                    .unwrap_or_else(|| self.input_remaining().len());
+                self.position += length;
                 self
             } else {
-                self.display_error("el_int");
+                self.record_error("prim_comment");
                 self
             }
         } else {
@@ -1081,27 +2854,41 @@ impl Parser {
         }
     }
 
-    ///floating point number, e.g. 12.34 or -123.45
-    pub fn el_float(mut self: Parser) -> Parser {
+    ///Matches a block comment, i.e. "/*" up to the matching "*/", which can be nested, e.g. "/* a /* b */ c */"<br />
+    ///Comments are discarded, so nothing is chomped. Fails if eof is reached before the comment is closed
+    pub fn prim_block_comment(mut self: Parser) -> Parser {
         if self.success {
-            let display_errors_previous_flag_setting = self.display_errors;
-            self.display_errors = false;
-            self = self
-                .combi_optional(|s: Parser| Parser::prim_word(s, "-"))
-                .combi_one_or_more_of(Parser::prim_digit)
-                .prim_word(".")
-                .combi_one_or_more_of(Parser::prim_digit);
-            self.display_errors = display_errors_previous_flag_setting;
-            if self.success {
-                let mut el = ParserElement::new();
-                let val = self.clone().chomp.parse().unwrap();
-                el.el_type = Some(ParserElementType::Float64);
-                el.float64 = Some(val);
-                self = self.output_arena_append_element(el);
-                self = self.chomp_clear();
+            if !self.input_remaining().starts_with("/*") {
+                self.success = false;
+                self.record_error("prim_block_comment");
+                return self;
+            }
+            let remaining = self.input_remaining();
+            let mut depth = 0;
+            let mut length = 0;
+            while length < remaining.len() {
+                if remaining[length..].starts_with("/*") {
+                    depth += 1;
+                    length += 2;
+                } else if remaining[length..].starts_with("*/") {
+                    depth -= 1;
+                    length += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    length += remaining[length..]
+                        .chars()
+                        .next()
+                        .map_or(1, |c| c.len_utf8());
+                }
+            }
+            if depth == 0 {
+                self.position += length;
                 self
             } else {
-                self.display_error("el_float");
+                self.success = false;
+                self.record_error("prim_block_comment - unterminated");
                 self
             }
         } else {
@@ -1109,417 +2896,5394 @@ impl Parser {
         }
     }
 
-    ///el_var name of prim_chars followed by a space, e.g. "x" or "lö̲ng_variablé_name"
-    pub fn el_var(mut self: Parser) -> Parser {
-        self = self.combi_one_or_more_of(Parser::prim_char).prim_word(" ");
+    ///Matches if you've reached the end of the parsed string, i.e. check for an empty string at this stage of the parser...
+    pub fn prim_eof(mut self: Parser) -> Parser {
+        if self.success && self.input_remaining().len() == 0 {
+            self
+        } else if self.success {
+            self.success = false;
+            self.record_error("prim_eof");
+            self
+        } else {
+            self.display_error("prim_eof");
+            self
+        }
+    }
+
+    ///Matches like [prim_eof](#method.prim_eof), but also if only whitespace (e.g. spaces, tabs or eols) remains, which it consumes without chomping<br />
+    ///e.g. as a final check on a file which ends with a trailing eol
+    pub fn prim_eof_ws(mut self: Parser) -> Parser {
+        if self.success && self.input_remaining().trim_start().is_empty() {
+            self.position = self.input_original.len();
+            self
+        } else if self.success {
+            self.success = false;
+            self.record_error("prim_eof_ws");
+            self
+        } else {
+            self.display_error("prim_eof_ws");
+            self
+        }
+    }
+
+    ///Matches either (prim_eolss)[#method.prim_eolss] or (prim_eof)[#method.prim_eof]<br />
+    ///Any trailing spaces or tabs before them are skipped with [prim_end_of_line_whitespace](#method.prim_end_of_line_whitespace)
+    pub fn prim_eols_or_eof(mut self: Parser) -> Parser {
         if self.success {
             let display_errors_previous_flag_setting = self.display_errors;
             self.display_errors = false;
-            let mut el = ParserElement::new();
-            let chomp = self.clone().chomp;
-            let el_var = chomp[..(chomp.len() - 1)].to_string();
-            el.el_type = Some(ParserElementType::Var);
-            el.var_name = Some(el_var);
-            self = self.output_arena_append_element(el);
-            //println!("{:?}", el);
-            self = self.chomp_clear();
-            self.display_errors = display_errors_previous_flag_setting;
-            self
+            let snap = self.snapshot();
+            self = self
+                .prim_end_of_line_whitespace()
+                .combi_first_success_of(&[Parser::prim_eols, Parser::prim_eof].to_vec());
+            if self.success {
+                self.display_errors = display_errors_previous_flag_setting;
+                self
+            } else {
+                self = self.restore(snap);
+                self.success = false;
+                self.record_error("prim_eols_or_eof");
+                self.display_errors = display_errors_previous_flag_setting;
+                self
+            }
         } else {
-            self.display_error("el_var");
             self
         }
     }
-}
 
-/// ## Parser Functions
-impl Parser {
-    ///equals sign, el_var name, value (test using el_int for now), e.g. "= x 1" (x equals 1)
-    pub fn fn_var_assign(self: Parser) -> Parser {
-        let mut temp_self = self
-            .clone()
-            .prim_word("= ")
-            .chomp_clear()
-            .el_var()
-            .combi_first_success_of(
+    ///Matches the end of a statement, i.e. a ";", or [prim_eols](#method.prim_eols), or [prim_eof](#method.prim_eof), in that order, without chomping it<br />
+    ///so statements can be separated by either, e.g. "= x 1; = y 2"<br />
+    ///Any spaces, tabs or a comment before it are skipped with [prim_end_of_line_whitespace](#method.prim_end_of_line_whitespace),
+    ///and after a ";" so are any spaces, tabs, a comment and eols, up to the next statement
+    pub fn prim_statement_end(mut self: Parser) -> Parser {
+        if self.success {
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            let snap = self.snapshot();
+            let chomping_previous_flag_setting = self.chomping;
+            self.chomping = false;
+            self = self.prim_end_of_line_whitespace().combi_first_success_of(
                 &[
-                    Parser::fn_var_sum,
-                    //el_float first so the number before . is not thought of as an el_int
-                    Parser::el_float,
-                    Parser::el_int,
+                    |s: Parser| {
+                        s.prim_word(";")
+                            .prim_end_of_line_whitespace()
+                            .combi_optional(Parser::prim_eols)
+                    },
+                    Parser::prim_eols,
+                    Parser::prim_eof,
                 ]
                 .to_vec(),
-            )
-            .prim_eols_or_eof();
-        if temp_self.success {
-            //get the previously parsed variable name, and variable value
-            let variable_el_option = temp_self.clone().output_arena_get_nth_last_child_element(1);
-            let value_el_option = temp_self.clone().output_arena_get_nth_last_child_element(0);
-            //combine them into one element
-            match (variable_el_option, value_el_option) {
-                (Some(variable_el), Some(mut value_el)) => {
-                    value_el.el_type = Some(ParserElementType::Var);
-                    value_el.var_name = variable_el.var_name;
-                    //remove those two last elements, and replace them with the combined element
-                    temp_self = temp_self.output_arena_remove_nth_last_child_element(0);
-                    temp_self = temp_self.output_arena_remove_nth_last_child_element(0);
-                    //add combined element back into arena
-                    temp_self = temp_self.output_arena_append_element(value_el);
-                    temp_self = temp_self.chomp_clear();
-                    temp_self
-                }
-                _ => {
-                    temp_self.display_error("fn_var_assign - no variable or value found to assign");
-                    temp_self
-                }
+            );
+            self.chomping = chomping_previous_flag_setting;
+            if self.success {
+                self.display_errors = display_errors_previous_flag_setting;
+                self
+            } else {
+                self = self.restore(snap);
+                self.success = false;
+                self.record_error("prim_statement_end");
+                self.display_errors = display_errors_previous_flag_setting;
+                self
             }
         } else {
-            temp_self.display_error("fn_var_assign");
-            temp_self
+            self
         }
     }
 
-    ///plus sign, value, value (both ints or both floats), e.g. "+ 1 2" (1 + 2 = 3) or "+ 1.2 3.4" (1.2 + 3.4 = 4.6)
-    pub fn fn_var_sum(mut self: Parser) -> Parser {
-        let mut original_self = self.clone();
-        let without_brackets = self
-            .clone()
-            .prim_word("+ ")
-            .chomp_clear()
-            .combi_first_success_of(
-                &[Parser::fn_var_sum, Parser::el_float, Parser::el_int].to_vec(),
-            )
-            .prim_word(" ")
-            .chomp_clear()
-            .combi_first_success_of(
-                &[Parser::fn_var_sum, Parser::el_float, Parser::el_int].to_vec(),
-            );
+    /// Matches a UTF-8 byte order mark (\u{FEFF}) if there is one, without chomping it, e.g. at the start of a file saved by some Windows editors<br />
+    /// Always succeeds
+    pub fn prim_bom(mut self: Parser) -> Parser {
+        if self.success && self.input_remaining().starts_with('\u{FEFF}') {
+            self.position += '\u{FEFF}'.len_utf8();
+        }
+        self
+    }
 
-        let with_brackets = self
-            .clone()
-            .prim_word("(+ ")
-            .chomp_clear()
-            .combi_first_success_of(
-                &[Parser::fn_var_sum, Parser::el_float, Parser::el_int].to_vec(),
-            )
-            .prim_word(" ")
-            .chomp_clear()
-            .combi_first_success_of(
-                &[Parser::fn_var_sum, Parser::el_float, Parser::el_int].to_vec(),
-            )
-            .prim_word(")");
+    /// Matches zero or more spaces or tabs, without chomping them<br />
+    /// Always succeeds
+    pub fn prim_spaces_or_tabs(self: Parser) -> Parser {
+        self.and_then(|mut s: Parser| {
+            let chomping_previous_flag_setting = s.chomping;
+            s.chomping = false;
+            s = s.combi_zero_or_more_of(|s| {
+                Parser::combi_first_success_of(s, &[Parser::prim_space, Parser::prim_tab].to_vec())
+            });
+            s.chomping = chomping_previous_flag_setting;
+            s
+        })
+    }
 
-        if without_brackets.success {
-            self = without_brackets;
-        } else if with_brackets.success {
-            self = with_brackets;
-        } else {
-            original_self.display_error("fn_var_sum");
-            original_self.success = false;
-            return original_self;
-        }
+    /// Matches one or more spaces or tabs, without chomping them, e.g. between two values which mustn't run together as in "1-2"
+    fn prim_spaces_or_tabs_separator(self: Parser) -> Parser {
+        self.and_then(|mut s: Parser| {
+            let chomping_previous_flag_setting = s.chomping;
+            s.chomping = false;
+            s = s.combi_one_or_more_of(|s| {
+                Parser::combi_first_success_of(s, &[Parser::prim_space, Parser::prim_tab].to_vec())
+            });
+            s.chomping = chomping_previous_flag_setting;
+            s
+        })
+    }
 
-        let mut el = ParserElement::new();
-        //check both values exist
-        let variable2_el_option = self.clone().output_arena_get_nth_last_child_element(0);
-        let variable1_el_option = self.clone().output_arena_get_nth_last_child_element(1);
-        match (variable1_el_option, variable2_el_option) {
-            (Some(variable1_el), Some(variable2_el)) => {
-                //check both values have the same element type
-                match (variable1_el.el_type, variable2_el.el_type) {
-                    (Some(el1_type), Some(el2_type)) => {
-                        if el1_type == el2_type {
-                            match el1_type {
-                                //if it's an el_int set the int64 of the first element to be the sum of the 2 ints
-                                //(because we will remove the second element)
-                                ParserElementType::Int64 => {
-                                    match (variable1_el.int64, variable2_el.int64) {
-                                        (Some(val1), Some(val2)) => {
-                                            el.el_type = Some(ParserElementType::Int64);
-                                            el.int64 = Some(val1 + val2);
-                                        }
-                                        (_, _) => {
-                                            original_self.success = false;
-                                            original_self //original_self
-                                                .display_error("fn_var_sum - can't find two Int64 values");
-                                            return original_self;
-                                        }
-                                    }
-                                }
-
-                                //can't sum strings
-                                ParserElementType::Str => {
-                                    original_self.success = false;
-                                    original_self //original_self
-                                        .display_error("fn_var_sum - can't sum strings");
-                                    return original_self;
-                                }
-
-                                //if it's an el_float set the Float64 of the first element to be the sum of the 2 floats
-                                //(because we will remove the second element)
-                                _ => {
-                                    match (variable1_el.float64, variable2_el.float64) {
-                                        (Some(val1), Some(val2)) => {
-                                            el.el_type = Some(ParserElementType::Float64);
-                                            el.float64 = Some(val1 + val2);
-                                        }
-                                        (_, _) => {
-                                            original_self.success = false;
-                                            original_self //original_self
-                                                .display_error("fn_var_sum - can't find two Float64 values");
-                                            return original_self;
-                                        }
-                                    }
-                                }
-                            }
+    /// Matches zero or more spaces, without chomping them, and returns how many it matched, e.g. the indentation at the start of a line<br />
+    /// Always succeeds
+    pub fn prim_count_spaces(self: Parser) -> (Parser, usize) {
+        self.prim_count_indent(None)
+    }
 
-                            //remove the last 2 value elements
-                            self = self.output_arena_remove_nth_last_child_element(0);
-                            self = self.output_arena_remove_nth_last_child_element(0);
-                            //add combined (sum) element back into arena
-                            self = self.output_arena_append_element(el);
-                            self = self.chomp_clear();
-                            self
-                        } else {
-                            self
-                        }
-                    }
-                    (_, _) => self,
+    /// Matches zero or more spaces, and tabs if there is a 'tab_width', without chomping them, and returns the width they add up to,
+    /// e.g. "\t  x" is 6 with a tab_width of Some(4), or 0 with None because tabs aren't matched<br />
+    /// Always succeeds
+    pub fn prim_count_indent(mut self: Parser, tab_width: Option<usize>) -> (Parser, usize) {
+        let mut width = 0;
+        if self.success {
+            let mut length = 0;
+            for c in self.input_remaining().chars() {
+                match (c, tab_width) {
+                    (' ', _) => width += 1,
+                    ('\t', Some(tab_width)) => width += tab_width,
+                    _ => break,
                 }
+                length += c.len_utf8();
             }
-            _ => {
-                original_self.display_error("fn_var_sum - can't find either or both values");
-                original_self.success = false;
-                original_self
-            }
+            self.position += length;
         }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    ///TODO Need a way to test equlity of expected_result
-    fn test_lang_one_of_all_lang_parsers() {
-        let language_string = ">";
-        let _expected_result = (
-            ParserFunctionType::TakesParser(Parser::prim_next),
-            ParserFunctionParam::None,
-        );
-        let p = Parser::new(language_string);
-        let result = Parser::lang_one_of_all_lang_parsers(p);
-        assert_eq!(result.input_original, language_string);
+        (self, width)
     }
 
-    #[test]
-    fn test_get_parser_function_by_name() {
-        assert_eq!(
-            Parser::get_parser_function_by_name(">".to_string()) == Parser::lang_prim_next,
-            true
-        );
+    /// Matches zero or more spaces or tabs, without chomping them, e.g. trailing whitespace before an end of line<br />
+    /// Also skips a trailing [prim_comment](#method.prim_comment) or [prim_block_comment](#method.prim_block_comment), e.g. "= x 1 // set x"<br />
+    /// Always succeeds
+    pub fn prim_end_of_line_whitespace(mut self: Parser) -> Parser {
+        if self.success {
+            let chomping_previous_flag_setting = self.chomping;
+            self.chomping = false;
+            self = self.combi_zero_or_more_of(|s| {
+                Parser::combi_first_success_of(
+                    s,
+                    &[
+                        Parser::prim_space,
+                        Parser::prim_tab,
+                        Parser::prim_comment,
+                        Parser::prim_block_comment,
+                    ]
+                    .to_vec(),
+                )
+            });
+            self.chomping = chomping_previous_flag_setting;
+            self
+        } else {
+            self
+        }
     }
-    //================================================================================
-    //Start Language Aliases Testing
-    //================================================================================
-
-    //lang_combinators
-
-    #[test]
-    fn test_lang_combi_one_or_more() {
-        //only combis of prims so far
-        let input_str = "aaaa";
-        let language_string = "1+a";
-        let result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "aaaa");
-        assert_eq!(result.success, true);
+}
+/// ## Parser combinators
+/// they will (TODO) Panic at an error -  used to combine multiple [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators)
+impl Parser {
+    ///Matches either one, or multiple of any one parser or combinator of parsers, i.e. [combi_at_least](#method.combi_at_least) 1
+    pub fn combi_one_or_more_of<F>(self: Parser, func: F) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        self.combi_at_least(1, func)
     }
 
-    #[test]
-    fn test_lang_prim_word() {
-        let input_str = "test";
-        let language_string = "'test'";
-        let result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        //assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "test");
-        assert_eq!(result.success, true);
+    ///Matches at least 'n' of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators), then as many more as it can,
+    ///like [combi_count](#method.combi_count)<br />
+    ///If there are fewer than 'n' it fails with "combi_at_least - expected at least n", leaving the parser, and the output_arena, as they were
+    pub fn combi_at_least<F>(mut self: Parser, n: usize, func: F) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let checkpoint = self.checkpoint();
+            let (mut result, count) = self.combi_count(func);
+            if count >= n {
+                result.release(checkpoint)
+            } else {
+                result = result.rollback(&checkpoint).release(checkpoint);
+                result.success = false;
+                result.record_error(format!("combi_at_least - expected at least {}", n).as_str());
+                result
+            }
+        } else {
+            self
+        }
     }
 
-    #[test]
-    fn test_lang_prim_eols_or_eof() {
-        let mut input_str = "\r\n\r\n\r\n!\n";
-        let mut language_string = ",@,";
-        let mut result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
+    ///Matches either zero, one or multiple of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators).<br />
+    ///It stops as soon as a match doesn't consume any input, e.g. [combi_optional](#method.combi_optional) or [prim_eof](#method.prim_eof).<br />
+    ///Beware, it will always succeed!
+    pub fn combi_zero_or_more_of<F>(mut self: Parser, func: F) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            //the attempts which fail are expected, so keep any earlier error rather than theirs
+            let error_previous = self.error.clone();
+            let error_position_previous = self.error_position;
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            while self.success {
+                let position = self.position;
+                self = func(self);
+                //stop if it matched without consuming anything, rather than loop forever
+                if self.success && self.position == position {
+                    break;
+                }
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            self.error = error_previous;
+            self.error_position = error_position_previous;
+            self.success = true;
+            self
+        } else {
+            self
+        }
+    }
+
+    ///Matches either zero, one or multiple of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators),
+    ///like [combi_zero_or_more_of](#method.combi_zero_or_more_of), but also returns how many times it matched.<br />
+    ///Beware, it will always succeed!
+    pub fn combi_count<F>(mut self: Parser, func: F) -> (Parser, usize)
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        let mut count = 0;
+        if self.success {
+            //the attempts which fail are expected, so keep any earlier error rather than theirs
+            let error_previous = self.error.clone();
+            let error_position_previous = self.error_position;
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            while self.success {
+                let position = self.position;
+                self = func(self);
+                if self.success {
+                    count += 1;
+                    //stop if it matched without consuming anything, rather than loop forever
+                    if self.position == position {
+                        break;
+                    }
+                }
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            self.error = error_previous;
+            self.error_position = error_position_previous;
+            self.success = true;
+            (self, count)
+        } else {
+            (self, count)
+        }
+    }
+
+    ///Matches either zero, one or multiple of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators)<br />
+    ///until it reaches the second supplied [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators)
+    pub fn combi_until_first_do_second<F>(mut self: Parser, first_and_second: Vec<F>) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            //the attempts which fail are expected, so keep any earlier error rather than theirs
+            let error_previous = self.error.clone();
+            let error_position_previous = self.error_position;
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            while self.success {
+                self = Parser::combi_first_success_of(self, &first_and_second);
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            self.error = error_previous;
+            self.error_position = error_position_previous;
+            self.success = true;
+            self
+        } else {
+            self
+        }
+    }
+
+    ///Matches any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators),
+    ///skipping any spaces or tabs before and after it, without chomping them, e.g. "  1  "
+    pub fn combi_token<F>(mut self: Parser, func: F) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            self = func(self.prim_spaces_or_tabs());
+            self.prim_spaces_or_tabs()
+        } else {
+            self
+        }
+    }
+
+    ///Matches any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators), then requires [prim_eof](#method.prim_eof),<br />
+    ///i.e. fails if it leaves any trailing input, rather than silently stopping part way through
+    pub fn combi_complete<F>(mut self: Parser, func: F) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            self = func(self);
+            if self.success && !self.input_remaining().is_empty() {
+                self.success = false;
+                self.record_error("combi_complete - trailing input");
+            }
+            self
+        } else {
+            self
+        }
+    }
+
+    ///Matches the 'body' parser repeatedly until the 'terminator' parser matches, which is also consumed, e.g. "abc]" with body [prim_char](#method.prim_char) and terminator "]"<br />
+    ///The terminator is checked first each time, so the body can't swallow it.<br />
+    ///Fails if eof is reached (or the body fails) before the terminator is found
+    pub fn combi_many_till<B, T>(mut self: Parser, body: B, terminator: T) -> Parser
+    where
+        B: Fn(Parser) -> Parser,
+        T: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let display_errors_previous_flag_setting = self.display_errors;
+            loop {
+                let checkpoint = self.checkpoint();
+                self.display_errors = false;
+                self = terminator(self);
+                self.display_errors = display_errors_previous_flag_setting;
+                if self.success {
+                    return self.release(checkpoint);
+                }
+                //undo the failed terminator before trying the body
+                self = self.rollback(&checkpoint).release(checkpoint);
+                let position = self.position;
+                if self.input_remaining().is_empty() {
+                    self.success = false;
+                    self.record_error("combi_many_till - terminator not found");
+                    return self;
+                }
+                self = body(self);
+                if !self.success {
+                    return self;
+                }
+                //stop if the body matched without consuming anything, rather than loop forever
+                if self.position == position {
+                    self.success = false;
+                    self.record_error("combi_many_till - body consumed nothing");
+                    return self;
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    ///Matches either one or zero of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators).<br />
+    ///Beware, it will always succeed!
+    pub fn combi_optional<F>(mut self: Parser, func: F) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let error_previous = self.error.clone();
+            let error_position_previous = self.error_position;
+            self = func(self);
+            if !self.success {
+                self.error = error_previous;
+                self.error_position = error_position_previous;
+            }
+            self.success = true;
+            self
+        } else {
+            self.display_error("combi_optional");
+            self
+        }
+    }
+
+    ///Matches either one or zero of any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators),
+    ///like [combi_optional](#method.combi_optional), but also returns whether it matched and consumed some input.<br />
+    ///Beware, it will always succeed!
+    pub fn combi_optional_flagged<F>(mut self: Parser, func: F) -> (Parser, bool)
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let position = self.position;
+            let error_previous = self.error.clone();
+            let error_position_previous = self.error_position;
+            self = func(self);
+            let matched = self.success && self.position > position;
+            if !self.success {
+                self.error = error_previous;
+                self.error_position = error_position_previous;
+            }
+            self.success = true;
+            (self, matched)
+        } else {
+            self.display_error("combi_optional_flagged");
+            (self, false)
+        }
+    }
+
+    ///Matches any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators),
+    ///then names the last element it appended, e.g. "age" for an [el_int](#method.el_int), like [fn_var_assign](#method.fn_var_assign) names a value<br />
+    ///If it succeeds without appending an element, nothing is named
+    pub fn combi_named<F>(mut self: Parser, name: &str, func: F) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let last_child_previous = self.output_arena_get_nth_last_child_id(0);
+            self = func(self);
+            if self.success && self.output_arena_get_nth_last_child_id(0) != last_child_previous {
+                self = self.map_last_element(|mut el| {
+                    el.var_name = Some(name.to_string());
+                    el
+                });
+            }
+            self
+        } else {
+            self.display_error("combi_named");
+            self
+        }
+    }
+
+    ///Matches any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators),
+    ///and also returns the text it added to the chomp, e.g. "123" from "123abc" with combi_one_or_more_of(prim_digit)<br />
+    ///The text is empty if it fails, or if 'func' clears the chomp
+    pub fn combi_recognize<F>(mut self: Parser, func: F) -> (Parser, String)
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let chomp_previous = self.chomp.clone();
+            self = func(self);
+            let recognized = if self.success {
+                self.chomp
+                    .strip_prefix(chomp_previous.as_str())
+                    .unwrap_or("")
+                    .to_string()
+            } else {
+                "".to_string()
+            };
+            (self, recognized)
+        } else {
+            self.display_error("combi_recognize");
+            (self, "".to_string())
+        }
+    }
+
+    ///Matches any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators) like [combi_optional](#method.combi_optional),
+    ///but if it fails, undoes the attempt and appends the 'default' element instead, e.g. a count of 1 when none is given<br />
+    ///Beware, it will always succeed!
+    pub fn combi_or_default<F>(mut self: Parser, func: F, default: ParserElement) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let checkpoint = self.checkpoint();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = func(self);
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                self.release(checkpoint)
+            } else {
+                self.rollback(&checkpoint)
+                    .release(checkpoint)
+                    .output_arena_append_element(default)
+            }
+        } else {
+            self.display_error("combi_or_default");
+            self
+        }
+    }
+
+    ///Matches any [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators),
+    ///but if it fails the error is the supplied 'message' instead of the inner function's name, e.g. "expected an integer" rather than "el_int"
+    pub fn expect<F>(mut self: Parser, func: F, message: &str) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = func(self);
+            self.display_errors = display_errors_previous_flag_setting;
+            if !self.success {
+                //the one place an inner parser's error is replaced on purpose, but only its message, so it still shows where it failed
+                let error_position = self.error_position;
+                self.record_error(message);
+                if error_position.is_some() {
+                    self.error_position = error_position;
+                }
+            }
+            self
+        } else {
+            self.display_error("expect");
+            self
+        }
+    }
+
+    ///Matches zero, one or multiple of the 'item' parser, like [combi_zero_or_more_of](#method.combi_zero_or_more_of),
+    ///then folds the elements they appended into one, starting from 'init', e.g. "1 2 3 4" into a sum of 10<br />
+    ///Only the folded element is left in the output_arena, so with no items it is just 'init'<br />
+    ///Beware, it will always succeed!
+    pub fn combi_fold<F>(
+        mut self: Parser,
+        item: F,
+        init: ParserElement,
+        combine: fn(ParserElement, ParserElement) -> ParserElement,
+    ) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let output_arena_children = self
+                .output_arena_node_parent_id
+                .children(&self.output_arena)
+                .count();
+            self = self.combi_zero_or_more_of(item);
+            let arena = &self.output_arena;
+            let folded = self
+                .output_arena_node_parent_id
+                .children(arena)
+                .skip(output_arena_children)
+                .filter_map(|node_id| arena.get(node_id))
+                .map(|node| node.get().clone())
+                .fold(init, combine);
+            self.output_arena_truncate_children(output_arena_children)
+                .output_arena_append_element(folded)
+        } else {
+            self.display_error("combi_fold");
+            self
+        }
+    }
+
+    ///Matches zero, one or multiple of the 'item' parser, each separated by the 'separator' parser, e.g. "1,2,3"<br />
+    ///A separator is only consumed if another item follows it.<br />
+    ///Beware, it will always succeed!
+    pub fn combi_separated_by<F, G>(mut self: Parser, item: F, separator: G) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+        G: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            let mut checkpoint = self.checkpoint();
+            self = item(self);
+            while self.success {
+                self = self.release(checkpoint);
+                checkpoint = self.checkpoint();
+                let position = self.position;
+                self = item(separator(self));
+                //stop if the separator and item matched without consuming anything, rather than loop forever
+                if self.success && self.position == position {
+                    self.success = false;
+                }
+            }
+            //undo the last failed, or empty, attempt
+            self = self.rollback(&checkpoint).release(checkpoint);
+            self.display_errors = display_errors_previous_flag_setting;
+            self
+        } else {
+            self
+        }
+    }
+
+    ///As [combi_separated_by](#method.combi_separated_by), but also consumes an optional trailing separator after the last item, e.g. "1,2,3,"<br />
+    ///Beware, it will always succeed!
+    pub fn combi_separated_by_trailing<F, G>(mut self: Parser, item: F, separator: G) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+        G: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let position = self.position;
+            self = self.combi_separated_by(item, &separator);
+            //only after at least one item
+            if self.position > position {
+                self = self.combi_optional(&separator);
+            }
+            self
+        } else {
+            self
+        }
+    }
+
+    ///Matches 'operand', then zero or more of 'op' followed by another 'operand', e.g. "1 + 2 + 3"<br />
+    ///After each extra operand the last two elements in the output_arena are folded left-to-right into one, using the 'reducer',
+    ///i.e. ((1 + 2) + 3).<br />
+    ///The chomp from 'op' is cleared before the next operand. Fails if the first operand fails, or the 'reducer' returns None
+    pub fn combi_chain_left<O, Op, R>(mut self: Parser, operand: O, op: Op, reducer: R) -> Parser
+    where
+        O: Fn(Parser) -> Parser,
+        Op: Fn(Parser) -> Parser,
+        R: Fn(ParserElement, ParserElement) -> Option<ParserElement>,
+    {
+        if self.success {
+            self = operand(self);
+            let display_errors_previous_flag_setting = self.display_errors;
+            while self.success {
+                let checkpoint = self.checkpoint();
+                self.display_errors = false;
+                self = operand(op(self).chomp_clear());
+                self.display_errors = display_errors_previous_flag_setting;
+                if !self.success {
+                    //no more operations, so undo the failed attempt
+                    self = self.rollback(&checkpoint).release(checkpoint);
+                    break;
+                }
+                self = self.release(checkpoint);
+                let right_el_option = self.output_arena_get_nth_last_child_element(0);
+                let left_el_option = self.output_arena_get_nth_last_child_element(1);
+                match (left_el_option, right_el_option) {
+                    (Some(left_el), Some(right_el)) => match reducer(left_el, right_el) {
+                        Some(el) => {
+                            //replace the last 2 elements with the folded one
+                            self = self
+                                .output_arena_remove_nth_last_child_element(0)
+                                .output_arena_remove_nth_last_child_element(0)
+                                .output_arena_append_element(el);
+                        }
+                        _ => {
+                            self.success = false;
+                            self.record_error("combi_chain_left - reducer failed");
+                        }
+                    },
+                    _ => {
+                        self.success = false;
+                        self.record_error("combi_chain_left - can't find both operands");
+                    }
+                }
+            }
+            self
+        } else {
+            self
+        }
+    }
+
+    ///Tries to match one of the parsers supplied in an array (vec) of [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators).
+    ///
+    ///It matches in the order supplied
+    ///
+    ///If they all fail, the error is from the one which got furthest before failing, e.g. "el_int" rather than a general fail,
+    ///as long as it got past the start, otherwise it is just "combi_first_success_of"
+    pub fn combi_first_success_of<F>(mut self: Parser, funcs: &Vec<F>) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            //only save the cheap parts of the state, rather than cloning the whole parser for every attempt
+            let checkpoint = self.checkpoint();
+            let snap = &checkpoint.snap;
+            let display_errors_previous_flag_setting = self.display_errors;
+            let mut furthest_error: Option<(String, usize)> = None;
+            let mut max_depth_exceeded = false;
+            for func in funcs {
+                self.display_errors = false;
+                self = func(self);
+                self.display_errors = display_errors_previous_flag_setting;
+                if self.success {
+                    return self.release(checkpoint);
+                }
+                max_depth_exceeded = max_depth_exceeded || self.max_depth_exceeded;
+                //only compare an error recorded by this attempt, not one from before it
+                let is_new_error =
+                    self.error_position != snap.error_position || self.error != snap.error;
+                if let (true, Some(error), Some(error_position)) =
+                    (is_new_error, &self.error, self.error_position)
+                {
+                    let is_further = match &furthest_error {
+                        Some((_, furthest_position)) => error_position > *furthest_position,
+                        None => error_position > snap.position,
+                    };
+                    if is_further {
+                        furthest_error = Some((error.clone(), error_position));
+                    }
+                }
+                //undo the failed attempt before trying the next one
+                self = self.rollback(&checkpoint);
+            }
+            self = self.release(checkpoint);
+            //pass the reason on, like the error, rather than a general fail at each level
+            if max_depth_exceeded {
+                self.max_depth_exceeded = true;
+            }
+            match furthest_error {
+                Some((error, error_position)) => {
+                    self.display_error(error.as_str());
+                    self.error = Some(error);
+                    self.error_position = Some(error_position);
+                }
+                None => self.record_error("combi_first_success_of"),
+            }
+            self.success = false;
+            return self;
+        } else {
+            return self;
+        };
+    }
+
+    ///Tries every one of the supplied [Parser primitives](#parser-primitives) or other [Parser combinators](#parser-combinators) from the same start,
+    ///and keeps the one which consumed the most input, e.g. "int" rather than "in" on "int", where [combi_first_success_of](#method.combi_first_success_of) would keep the first match<br />
+    ///Ties go to the earliest in the order supplied. Each is tried and undone, then the longest is run again, so they shouldn't depend on anything outside the parser
+    pub fn combi_longest_of<F>(mut self: Parser, funcs: &[F]) -> Parser
+    where
+        F: Fn(Parser) -> Parser,
+    {
+        if self.success {
+            let checkpoint = self.checkpoint();
+            let display_errors_previous_flag_setting = self.display_errors;
+            let mut longest: Option<(&F, usize)> = None;
+            for func in funcs {
+                self.display_errors = false;
+                self = func(self);
+                self.display_errors = display_errors_previous_flag_setting;
+                let is_longer = match longest {
+                    Some((_, longest_position)) => self.position > longest_position,
+                    None => true,
+                };
+                if self.success && is_longer {
+                    longest = Some((func, self.position));
+                }
+                //undo each attempt, so the next one starts from the same place
+                self = self.rollback(&checkpoint);
+            }
+            self = self.release(checkpoint);
+            match longest {
+                Some((func, _)) => {
+                    self.display_errors = false;
+                    self = func(self);
+                    self.display_errors = display_errors_previous_flag_setting;
+                    self
+                }
+                None => {
+                    self.record_error("combi_longest_of");
+                    self.success = false;
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+}
+
+/// ## Parser Elements
+
+impl Parser {
+    ///Parses whichever element the [Parseable](trait.Parseable.html) type T parses, e.g. parser.element::&lt;i64&gt;() is the same as [el_int](#method.el_int)
+    pub fn element<T: Parseable>(self: Parser) -> Parser {
+        T::parse(self)
+    }
+
+    ///string, e.g. "123" or "The quick brown fox jumps over the lazy dog"<br />
+    ///escape sequences \n \t \r \" and \\ are decoded before being stored, any other escape fails<br />
+    ///(the chomp keeps the raw text, the decoded string is built separately)
+    pub fn el_str(mut self: Parser) -> Parser {
+        if self.success {
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.prim_quote();
+            let mut val = "".to_string();
+            let mut error = "el_str";
+            while self.success {
+                let next_option = self
+                    .input_remaining()
+                    .graphemes(true)
+                    .next()
+                    .map(|g| g.to_string());
+                match next_option {
+                    Some(next) if next == "\"" => {
+                        self = self.prim_quote();
+                        break;
+                    }
+                    Some(next) if next == "\\" => {
+                        self = self.prim_next();
+                        let escaped_option = match self.input_remaining().graphemes(true).next() {
+                            Some("n") => Some("\n"),
+                            Some("t") => Some("\t"),
+                            Some("r") => Some("\r"),
+                            Some("\"") => Some("\""),
+                            Some("\\") => Some("\\"),
+                            _ => None,
+                        };
+                        match escaped_option {
+                            Some(escaped) => {
+                                val += escaped;
+                                self = self.prim_next();
+                            }
+                            None => {
+                                error = "el_str - invalid escape";
+                                self.success = false;
+                            }
+                        }
+                    }
+                    Some(next) => {
+                        val += next.as_str();
+                        self = self.prim_next();
+                    }
+                    None => {
+                        self.success = false;
+                    }
+                }
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                let el = ParserElement::new_str(val);
+                self = self.output_arena_append_element(el);
+                self = self.chomp_clear();
+                self
+            } else {
+                self.record_error(error);
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///integer number, e.g. 12, +12 or -123456<br />
+    ///digits can be grouped with single underscores between them, e.g. 1_000_000, but not _1, 1_ or 1__0<br />
+    ///a 0x, 0b or 0o prefix is a hexadecimal, binary or octal number, e.g. 0xFF (255), 0b1010 (10), 0o17 (15), and the sign applies to them too, e.g. -0x10 (-16)<br />
+    ///fails if the number is too large for an Int64
+    pub fn el_int(mut self: Parser) -> Parser {
+        if self.success {
+            let snap = self.snapshot();
+            let chomp_length = self.chomp.len();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.prim_sign();
+            let sign_length = self.chomp.len();
+            let radix = match self.input_remaining().get(0..2) {
+                Some("0x") => 16,
+                Some("0b") => 2,
+                Some("0o") => 8,
+                _ => 10,
+            };
+            if radix != 10 {
+                self = self.prim_take(2);
+            }
+            let digits_start = self.chomp.len();
+            let is_digit = |c: char| c.is_digit(radix);
+            self = self
+                .combi_one_or_more_of(|s: Parser| s.prim_char_if(is_digit))
+                .combi_zero_or_more_of(|s: Parser| {
+                    s.prim_word("_")
+                        .combi_one_or_more_of(|s: Parser| s.prim_char_if(is_digit))
+                });
+            //an underscore which isn't between digits
+            if self.success && (self.chomp.ends_with('_') || self.starts_with("_")) {
+                self.success = false;
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                let sign = &self.chomp[chomp_length..sign_length];
+                let digits = self.chomp[digits_start..].replace('_', "");
+                match i64::from_str_radix(format!("{}{}", sign, digits).as_str(), radix) {
+                    Ok(val) => {
+                        let el = ParserElement::new_int(val);
+                        self = self.output_arena_append_element(el);
+                        self = self.chomp_clear();
+                        self
+                    }
+                    _ => {
+                        self = self.restore(snap);
+                        self.success = false;
+                        self.record_error("el_int - too large");
+                        self
+                    }
+                }
+            } else {
+                //nothing is consumed on any failure, e.g. the "0x" of "0xZ"
+                self = self.restore(snap);
+                self.success = false;
+                self.record_error("el_int");
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///unsigned integer number, e.g. 12 or 123456, but not -123 (no sign is allowed), for e.g. counts or indexes<br />
+    ///still produces an Int64 element, so it fails if the number is too large for one
+    pub fn el_uint(mut self: Parser) -> Parser {
+        if self.success {
+            let position = self.position;
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.combi_one_or_more_of(Parser::prim_digit);
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                match self.chomp.parse() {
+                    Ok(val) => {
+                        let el = ParserElement::new_int(val);
+                        self = self.output_arena_append_element(el);
+                        self = self.chomp_clear();
+                        self
+                    }
+                    _ => {
+                        self.position = position;
+                        self.success = false;
+                        self.record_error("el_uint - too large");
+                        self.chomp_clear()
+                    }
+                }
+            } else {
+                self.record_error("el_uint");
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///floating point number, e.g. 12.34, +12.34 or -123.45<br />
+    ///either side of the point can be left out, e.g. .5 (0.5) or 5. (5.0), but not both<br />
+    ///digits can be grouped with single underscores between them, e.g. 1_000.5, but not 1_.5, and it can have an exponent, e.g. 1.5e-3 or 1_000.5E+3<br />
+    ///a number without a point needs an exponent to be a float, e.g. 1e5, otherwise it is left for [el_int](#method.el_int)
+    pub fn el_float(mut self: Parser) -> Parser {
+        if self.success {
+            let snap = self.snapshot();
+            let chomp_length = self.chomp.len();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.prim_sign().combi_first_success_of(
+                &[
+                    |s: Parser| {
+                        s.prim_grouped_digits()
+                            .prim_word(".")
+                            .combi_optional(Parser::prim_grouped_digits)
+                    },
+                    |s: Parser| s.prim_word(".").prim_grouped_digits(),
+                    |s: Parser| s.prim_grouped_digits().prim_exponent(),
+                ]
+                .to_vec(),
+            );
+            //an optional exponent after the point, which is undone if it isn't complete, e.g. the "e" of "1.5e"
+            if self.success && !self.chomp[chomp_length..].contains(['e', 'E']) {
+                let exponent_snap = self.snapshot();
+                self = self.prim_exponent();
+                if !self.success {
+                    self = self.restore(exponent_snap);
+                }
+            }
+            //an underscore which isn't between digits
+            let text = self.chomp[chomp_length..].to_string();
+            let bytes = text.as_bytes();
+            let underscores_between_digits = bytes.iter().enumerate().all(|(i, b)| {
+                *b != b'_'
+                    || (i > 0
+                        && bytes[i - 1].is_ascii_digit()
+                        && matches!(bytes.get(i + 1), Some(next) if next.is_ascii_digit()))
+            });
+            if !underscores_between_digits || self.starts_with("_") {
+                self.success = false;
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            let val_option = if self.success {
+                text.replace('_', "").parse::<f64>().ok()
+            } else {
+                None
+            };
+            match val_option {
+                Some(val) => {
+                    let el = ParserElement::new_float(val);
+                    self = self.output_arena_append_element(el);
+                    self = self.chomp_clear();
+                    self
+                }
+                None => {
+                    self = self.restore(snap);
+                    self.success = false;
+                    self.record_error("el_float");
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    ///special float, i.e. "inf", "+inf", "-inf" or "nan", e.g. from scientific data<br />
+    ///appends a Float64 element with f64::INFINITY, f64::NEG_INFINITY or f64::NAN, and only matches whole words, so not the start of "info"<br />
+    ///Beware, NaN is never equal to anything, not even itself, so check it with is_nan() rather than assert_eq!
+    pub fn el_float_special(mut self: Parser) -> Parser {
+        if self.success {
+            let chomp_length = self.chomp.len();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.combi_first_success_of(
+                &[
+                    |s: Parser| s.prim_keyword("inf"),
+                    |s: Parser| s.prim_keyword("+inf"),
+                    |s: Parser| s.prim_keyword("-inf"),
+                    |s: Parser| s.prim_keyword("nan"),
+                ]
+                .to_vec(),
+            );
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                let val = match &self.chomp[chomp_length..] {
+                    "-inf" => f64::NEG_INFINITY,
+                    "nan" => f64::NAN,
+                    _ => f64::INFINITY,
+                };
+                self.output_arena_append_element(ParserElement::new_float(val))
+                    .chomp_clear()
+            } else {
+                self.record_error("el_float_special");
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///digits grouped with underscores, e.g. "1_000", for [el_float](#method.el_float)<br />
+    ///like el_int, an underscore not followed by digits is left consumed, so check the chomp afterwards
+    fn prim_grouped_digits(self: Parser) -> Parser {
+        self.combi_one_or_more_of(Parser::prim_digit)
+            .combi_zero_or_more_of(|s: Parser| {
+                s.prim_word("_").combi_one_or_more_of(Parser::prim_digit)
+            })
+    }
+
+    ///the exponent of a float, e.g. "e-3" or "E+10", for [el_float](#method.el_float)
+    fn prim_exponent(self: Parser) -> Parser {
+        self.prim_word_oneof(&["e", "E"])
+            .prim_sign()
+            .combi_one_or_more_of(Parser::prim_digit)
+    }
+
+    ///boolean, i.e. "true" or "false", as a whole word, so not the start of e.g. "trueish"
+    pub fn el_bool(mut self: Parser) -> Parser {
+        if self.success {
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            let chomp_length = self.chomp.len();
+            self = self.combi_first_success_of(
+                &[
+                    |s: Parser| s.prim_keyword("true"),
+                    |s: Parser| s.prim_keyword("false"),
+                ]
+                .to_vec(),
+            );
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                let val = &self.chomp[chomp_length..] == "true";
+                self.output_arena_append_element(ParserElement::new_bool(val))
+                    .chomp_clear()
+            } else {
+                self.record_error("el_bool");
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///null, i.e. the [NULL_WORD](constant.NULL_WORD.html), e.g. "null", as a whole word, so not the start of e.g. "nullable"<br />
+    ///appends a Null element, which has no value
+    pub fn el_null(mut self: Parser) -> Parser {
+        if self.success {
+            self = self.prim_keyword(NULL_WORD);
+            if self.success {
+                self.output_arena_append_element(ParserElement::new_null())
+                    .chomp_clear()
+            } else {
+                self.record_error("el_null");
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///character in single quotes, e.g. 'a'<br />
+    ///escape sequences \n \t \\ and \' are decoded, and it fails if there isn't exactly one character, e.g. '' or 'ab'
+    pub fn el_char(mut self: Parser) -> Parser {
+        if self.success {
+            let snap = self.snapshot();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.prim_quote_single();
+            let mut error = "el_char";
+            let mut char_option = None;
+            if self.success {
+                let mut chars = self.input_remaining().chars();
+                char_option = match (chars.next(), chars.next()) {
+                    (Some('\\'), Some('n')) => Some(('\n', 2)),
+                    (Some('\\'), Some('t')) => Some(('\t', 2)),
+                    (Some('\\'), Some('\\')) => Some(('\\', 2)),
+                    (Some('\\'), Some('\'')) => Some(('\'', 2)),
+                    (Some('\\'), _) => {
+                        error = "el_char - invalid escape";
+                        None
+                    }
+                    (Some('\''), _) => {
+                        error = "el_char - empty";
+                        None
+                    }
+                    (Some(c), _) => Some((c, 1)),
+                    _ => None,
+                };
+                match char_option {
+                    Some((_, length)) => {
+                        //match the chars rather than graphemes, so e.g. an e with a combining accent is more than one character
+                        let literal: String = self.input_remaining().chars().take(length).collect();
+                        self = self.prim_word(literal.as_str()).prim_quote_single();
+                        if !self.success {
+                            error = "el_char - more than one character";
+                        }
+                    }
+                    None => self.success = false,
+                }
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            match char_option {
+                Some((c, _)) if self.success => self
+                    .output_arena_append_element(ParserElement::new_char(c))
+                    .chomp_clear(),
+                _ => {
+                    self = self.restore(snap);
+                    self.success = false;
+                    self.record_error(error);
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    ///duration of one or more numbers each followed by a unit of h, m or s, e.g. "3h30m" or "45s"<br />
+    ///appends a Duration element of the total number of seconds, e.g. 12600 or 45
+    pub fn el_duration(mut self: Parser) -> Parser {
+        if self.success {
+            let snap = self.snapshot();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            let mut total: i64 = 0;
+            let mut parts = 0;
+            let mut error_option = None;
+            while error_option.is_none()
+                && self
+                    .input_remaining()
+                    .starts_with(|c: char| c.is_ascii_digit())
+            {
+                let chomp_length = self.chomp.len();
+                self = self.combi_one_or_more_of(Parser::prim_digit);
+                let number_option: Option<i64> = self.chomp[chomp_length..].parse().ok();
+                let unit_option = match self.input_remaining().chars().next() {
+                    Some('h') => Some(3600),
+                    Some('m') => Some(60),
+                    Some('s') => Some(1),
+                    _ => None,
+                };
+                match unit_option {
+                    Some(unit) => {
+                        self = self.prim_next();
+                        match number_option
+                            .and_then(|number| number.checked_mul(unit))
+                            .and_then(|seconds| total.checked_add(seconds))
+                        {
+                            Some(new_total) => {
+                                total = new_total;
+                                parts += 1;
+                            }
+                            None => error_option = Some("el_duration - too large"),
+                        }
+                    }
+                    None => error_option = Some("el_duration - missing unit"),
+                }
+            }
+            if parts == 0 && error_option.is_none() {
+                error_option = Some("el_duration");
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            match error_option {
+                None => self
+                    .output_arena_append_element(ParserElement::new_duration(total))
+                    .chomp_clear(),
+                Some(error) => {
+                    self = self.restore(snap);
+                    self.success = false;
+                    self.record_error(error);
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+
+    ///list in square brackets of comma separated elements, e.g. "[1, 2.5, \"three\"]" or "[]"<br />
+    ///appends a List element, with each item (an [el_float](#method.el_float), [el_int](#method.el_int), [el_str](#method.el_str) or a nested el_list) as its children
+    pub fn el_list(mut self: Parser) -> Parser {
+        if self.success {
+            let checkpoint = self.checkpoint();
+            let display_errors_previous_flag_setting = self.display_errors;
+            self.display_errors = false;
+            self = self.prim_word("[").prim_spaces_or_tabs();
+            if self.success {
+                let outer_parent_id = self.output_arena_node_parent_id;
+                self = self
+                    .output_arena_append_element(ParserElement::new_list())
+                    .chomp_clear();
+                //the list becomes the current parent, so the items are appended to it
+                if let Some(list_id) = self
+                    .output_arena
+                    .get(outer_parent_id)
+                    .and_then(|node| node.last_child())
+                {
+                    self.output_arena_node_parent_id = list_id;
+                }
+                self = self
+                    .combi_separated_by(
+                        |s: Parser| {
+                            s.combi_first_success_of(
+                                &[
+                                    Parser::el_float,
+                                    Parser::el_int,
+                                    Parser::el_str,
+                                    Parser::el_list,
+                                ]
+                                .to_vec(),
+                            )
+                        },
+                        |s: Parser| {
+                            s.prim_spaces_or_tabs()
+                                .prim_word(",")
+                                .prim_spaces_or_tabs()
+                                .chomp_clear()
+                        },
+                    )
+                    .prim_spaces_or_tabs()
+                    .prim_word("]");
+                self.output_arena_node_parent_id = outer_parent_id;
+            }
+            self.display_errors = display_errors_previous_flag_setting;
+            if self.success {
+                self.release(checkpoint).chomp_clear()
+            } else {
+                //undo the attempt, so the partly built List isn't left in the output_arena
+                let (error, error_position) = (self.error.take(), self.error_position);
+                self.rollback(&checkpoint)
+                    .release(checkpoint)
+                    .fail_passing_on_error(error, error_position, "el_list")
+            }
+        } else {
+            self
+        }
+    }
+
+    ///el_var name followed by a space, e.g. "x" or "lö̲ng_variablé_name"<br />
+    ///with an optional type annotation of int, float, str or bool, e.g. "x:int"<br />
+    ///the name must start with a letter (any unicode letter, e.g. "é") or an underscore, so not e.g. "1x"
+    pub fn el_var(mut self: Parser) -> Parser {
+        let chomp_length = self.chomp.len();
+        self = self.el_var_name();
+        if self.success {
+            let mut el = ParserElement::new_var(
+                self.chomp[chomp_length..].to_string(),
+                ParserElement::new(),
+            );
+            if self.starts_with(":") {
+                let type_name: String = self.input_remaining()[1..]
+                    .chars()
+                    .take_while(|c| !c.is_whitespace())
+                    .collect();
+                match ParserElementType::from_type_name(&type_name) {
+                    Some(var_type) => {
+                        el.var_type = Some(var_type);
+                        self = self.prim_word(format!(":{}", type_name).as_str());
+                    }
+                    _ => {
+                        self.success = false;
+                        self.record_error("el_var - unknown type annotation");
+                        return self;
+                    }
+                }
+            }
+            self = self.prim_word(" ");
+            if self.success {
+                self = self.output_arena_append_element(el);
+                self.chomp_clear()
+            } else {
+                self.record_error("el_var");
+                self
+            }
+        } else {
+            self
+        }
+    }
+
+    ///the name part of [el_var](#method.el_var), up to any whitespace, ':' or ';'<br />
+    ///shared with [fn_print](#method.fn_print), so any name that can be assigned can also be printed
+    fn el_var_name(mut self: Parser) -> Parser {
+        if self.success {
+            let name: String = self
+                .input_remaining()
+                .graphemes(true)
+                .take_while(|g| !g.starts_with(|c: char| c.is_whitespace() || c == ':' || c == ';'))
+                .collect();
+            match self.input_remaining().chars().next() {
+                Some(c) if c.is_alphabetic() || c == '_' => self.prim_word(name.as_str()),
+                Some(c) if !c.is_whitespace() => {
+                    self.success = false;
+                    self.record_error("el_var - invalid name");
+                    self
+                }
+                _ => {
+                    self.success = false;
+                    self.record_error("el_var");
+                    self
+                }
+            }
+        } else {
+            self
+        }
+    }
+}
+
+/// ## Parser Functions
+impl Parser {
+    ///equals sign, el_var name, value (a number, string or bool, or a function giving one, e.g. "+ 1 2"), e.g. "= x 1" (x equals 1)
+    pub fn fn_var_assign(self: Parser) -> Parser {
+        let mut temp_self = self
+            .clone()
+            .prim_word("= ")
+            .chomp_clear()
+            .el_var()
+            .combi_first_success_of(
+                &[
+                    Parser::fn_var_sum,
+                    Parser::fn_var_sub,
+                    Parser::fn_var_mul,
+                    Parser::fn_var_div,
+                    Parser::fn_negate,
+                    Parser::fn_if,
+                    Parser::fn_equals,
+                    //el_float first so the number before . is not thought of as an el_int
+                    Parser::el_float,
+                    Parser::el_int,
+                    Parser::el_str,
+                    Parser::el_bool,
+                ]
+                .to_vec(),
+            )
+            .prim_statement_end();
+        if temp_self.success {
+            //get the previously parsed variable name, and variable value
+            let variable_id_option = temp_self.output_arena_get_nth_last_child_id(1);
+            let value_id_option = temp_self.output_arena_get_nth_last_child_id(0);
+            let value_type = value_id_option.and_then(|id| temp_self.output_arena_value_type(id));
+            let variable_el_option = temp_self.output_arena_get_nth_last_child_element(1);
+            let value_el_option = temp_self.output_arena_get_nth_last_child_element(0);
+            //combine them into one element
+            match (variable_el_option, value_el_option) {
+                //the value must match the type annotation, if there is one
+                (Some(variable_el), Some(_))
+                    if variable_el.var_type.is_some() && variable_el.var_type != value_type =>
+                {
+                    temp_self.success = false;
+                    temp_self.record_error(
+                        format!(
+                            "fn_var_assign - {:?} is declared as {:?} but was assigned {:?}",
+                            variable_el.var_name, variable_el.var_type, value_type
+                        )
+                        .as_str(),
+                    );
+                    temp_self
+                }
+                //a Sum or BinaryOp is kept as the child of the variable, until it is combined by evaluate
+                (Some(_), Some(value_el))
+                    if value_el.el_type == Some(ParserElementType::Sum)
+                        || value_el.el_type == Some(ParserElementType::BinaryOp) =>
+                {
+                    if let (Some(variable_id), Some(value_id)) =
+                        (variable_id_option, value_id_option)
+                    {
+                        temp_self.arenas_before_edit(&[variable_id, value_id], &[]);
+                        let arena = &mut temp_self.output_arena;
+                        value_id.detach(arena);
+                        variable_id.append(value_id, arena);
+                    }
+                    temp_self = temp_self.chomp_clear();
+                    temp_self
+                }
+                (Some(variable_el), Some(mut value_el)) => {
+                    value_el.el_type = Some(ParserElementType::Var);
+                    value_el.var_name = variable_el.var_name;
+                    value_el.var_type = variable_el.var_type;
+                    //remove those two last elements, and replace them with the combined element
+                    temp_self = temp_self.output_arena_remove_nth_last_child_element(0);
+                    temp_self = temp_self.output_arena_remove_nth_last_child_element(0);
+                    //add combined element back into arena
+                    temp_self = temp_self.output_arena_append_element(value_el);
+                    temp_self = temp_self.chomp_clear();
+                    temp_self
+                }
+                _ => {
+                    temp_self.record_error("fn_var_assign - no variable or value found to assign");
+                    temp_self
+                }
+            }
+        } else {
+            self.fail_passing_on(&temp_self, "fn_var_assign")
+        }
+    }
+
+    ///print, el_var name, e.g. "print x" (output the value of x)<br />
+    ///appends a Print element holding the current value of the variable
+    pub fn fn_print(self: Parser) -> Parser {
+        let mut temp_self = self.clone().prim_word("print ").chomp_clear().el_var_name();
+        let var_name = temp_self.chomp.clone();
+        temp_self = temp_self.chomp_clear().prim_statement_end();
+        if temp_self.success {
+            //the variable might still hold a Sum, so evaluate it to get its current value
+            match temp_self
+                .output_arena_find_var_node_id(var_name.as_str())
+                .map(|node_id| temp_self.output_arena_evaluate_node(node_id))
+            {
+                Some(Ok(mut value_el)) => {
+                    value_el.el_type = Some(ParserElementType::Print);
+                    value_el.var_name = None;
+                    temp_self = temp_self.output_arena_append_element(value_el);
+                    temp_self = temp_self.chomp_clear();
+                    temp_self
+                }
+                Some(Err(error)) => {
+                    temp_self.success = false;
+                    temp_self.record_error(error.as_str());
+                    temp_self
+                }
+                _ => {
+                    temp_self.success = false;
+                    temp_self.record_error("fn_print - undefined variable");
+                    temp_self
+                }
+            }
+        } else {
+            self.fail_passing_on(&temp_self, "fn_print")
+        }
+    }
+
+    ///open curly bracket, e.g. "{" starts a new scope, until the matching [fn_scope_end](#method.fn_scope_end)<br />
+    ///appends a Scope element, which becomes the parent of any elements until the scope ends,
+    ///so variables assigned inside it don't leak out
+    pub fn fn_scope_begin(self: Parser) -> Parser {
+        let mut temp_self = self.clone().prim_word("{").prim_statement_end();
+        if temp_self.success {
+            let mut el = ParserElement::new();
+            el.el_type = Some(ParserElementType::Scope);
+            temp_self = temp_self.output_arena_append_element(el).chomp_clear();
+            //the new scope becomes the current parent
+            if let Some(scope_id) = temp_self
+                .output_arena
+                .get(temp_self.output_arena_node_parent_id)
+                .and_then(|node| node.last_child())
+            {
+                temp_self.output_arena_node_parent_id = scope_id;
+            }
+            temp_self
+        } else {
+            self.fail_passing_on(&temp_self, "fn_scope_begin")
+        }
+    }
+
+    ///close curly bracket, e.g. "}" ends the current scope started by [fn_scope_begin](#method.fn_scope_begin)<br />
+    ///fails if there is no scope to end
+    pub fn fn_scope_end(self: Parser) -> Parser {
+        let mut temp_self = self.clone().prim_word("}").prim_statement_end();
+        if temp_self.success {
+            let arena = &temp_self.output_arena;
+            let current_id = temp_self.output_arena_node_parent_id;
+            let parent_option = arena.get(current_id).and_then(|node| node.parent());
+            match parent_option {
+                Some(parent_id) => {
+                    //the outer scope becomes the current parent again
+                    temp_self.output_arena_node_parent_id = parent_id;
+                    temp_self.chomp_clear()
+                }
+                _ => {
+                    let mut original_self = self;
+                    original_self.success = false;
+                    original_self.record_error("fn_scope_end - no scope to end");
+                    original_self
+                }
+            }
+        } else {
+            self.fail_passing_on(&temp_self, "fn_scope_end")
+        }
+    }
+
+    ///dot, string, string, e.g. ". "foo" "bar"" ("foo" joined to "bar" = "foobar")<br />
+    ///either string can be another nested fn_str_concat, e.g. ". "a" . "b" "c"" ("abc")
+    pub fn fn_str_concat(mut self: Parser) -> Parser {
+        let mut original_self = self.clone();
+        self = self
+            .prim_word(". ")
+            .chomp_clear()
+            .combi_first_success_of(&[Parser::fn_str_concat, Parser::el_str].to_vec())
+            .prim_word(" ")
+            .chomp_clear()
+            .combi_first_success_of(&[Parser::fn_str_concat, Parser::el_str].to_vec());
+        if !self.success {
+            return original_self.fail_passing_on(&self, "fn_str_concat");
+        }
+
+        let variable2_el_option = self.output_arena_get_nth_last_child_element(0);
+        let variable1_el_option = self.output_arena_get_nth_last_child_element(1);
+        match (variable1_el_option, variable2_el_option) {
+            (Some(variable1_el), Some(variable2_el)) => {
+                match (
+                    variable1_el.el_type,
+                    variable1_el.string,
+                    variable2_el.el_type,
+                    variable2_el.string,
+                ) {
+                    (
+                        Some(ParserElementType::Str),
+                        Some(val1),
+                        Some(ParserElementType::Str),
+                        Some(val2),
+                    ) => {
+                        let el = ParserElement::new_str(val1 + val2.as_str());
+                        //remove the last 2 string elements
+                        self = self.output_arena_remove_nth_last_child_element(0);
+                        self = self.output_arena_remove_nth_last_child_element(0);
+                        //add combined (concatenated) element back into arena
+                        self = self.output_arena_append_element(el);
+                        self = self.chomp_clear();
+                        self
+                    }
+                    _ => {
+                        original_self.success = false;
+                        original_self.record_error("fn_str_concat - can only concat strings");
+                        original_self
+                    }
+                }
+            }
+            _ => {
+                original_self.record_error("fn_str_concat - can't find either or both values");
+                original_self.success = false;
+                original_self
+            }
+        }
+    }
+
+    ///double equals sign, value, value (ints, floats, strings, chars or bools), e.g. "== 1 1" (true) or "== 1 2" (false)<br />
+    ///appends a Bool element of whether the values are equal, and fails if they are different types
+    pub fn fn_equals(mut self: Parser) -> Parser {
+        let mut original_self = self.clone();
+        let value = |s: Parser| {
+            s.combi_first_success_of(
+                &[
+                    Parser::el_float,
+                    Parser::el_int,
+                    Parser::el_str,
+                    Parser::el_char,
+                    Parser::el_bool,
+                ]
+                .to_vec(),
+            )
+        };
+        //at least one space or tab between the values, as for fn_binary_op
+        self = self
+            .prim_word("== ")
+            .chomp_clear()
+            .prim_spaces_or_tabs()
+            .and_then(value)
+            .prim_spaces_or_tabs_separator()
+            .chomp_clear()
+            .and_then(value);
+        if !self.success {
+            return original_self.fail_passing_on(&self, "fn_equals");
+        }
+        let el2_option = self
+            .output_arena_get_nth_last_child_element(0)
+            .map(ParserEl::from);
+        let el1_option = self
+            .output_arena_get_nth_last_child_element(1)
+            .map(ParserEl::from);
+        match (el1_option, el2_option) {
+            (Some(el1), Some(el2)) if el1.el_type() == el2.el_type() => {
+                let children = self
+                    .output_arena_node_parent_id
+                    .children(&self.output_arena)
+                    .count();
+                self.output_arena_truncate_children(children - 2)
+                    .output_arena_append_element(ParserElement::new_bool(
+                        el1.value() == el2.value(),
+                    ))
+                    .chomp_clear()
+            }
+            _ => {
+                original_self.success = false;
+                original_self.record_error("fn_equals - can't compare different types");
+                original_self
+            }
+        }
+    }
+
+    ///if, condition (a bool or [fn_equals](#method.fn_equals)), then value, else value, e.g. "if == 1 1 42 0" (42)<br />
+    ///both values are parsed, to check they are valid, but only the one selected by the condition is kept
+    pub fn fn_if(mut self: Parser) -> Parser {
+        let mut original_self = self.clone();
+        let value = |s: Parser| {
+            s.combi_first_success_of(
+                &[
+                    Parser::fn_var_sum,
+                    Parser::fn_var_sub,
+                    Parser::fn_var_mul,
+                    Parser::fn_var_div,
+                    Parser::fn_negate,
+                    Parser::el_float,
+                    Parser::el_int,
+                    Parser::el_str,
+                ]
+                .to_vec(),
+            )
+        };
+        //at least one space or tab between the condition and each value, as for fn_binary_op
+        self = self
+            .prim_word("if ")
+            .chomp_clear()
+            .prim_spaces_or_tabs()
+            .combi_first_success_of(&[Parser::fn_equals, Parser::el_bool].to_vec())
+            .prim_spaces_or_tabs_separator()
+            .chomp_clear()
+            .and_then(value)
+            .prim_spaces_or_tabs_separator()
+            .chomp_clear()
+            .and_then(value)
+            .chomp_clear();
+        if !self.success {
+            return original_self.fail_passing_on(&self, "fn_if");
+        }
+        let condition_option = self
+            .output_arena_get_nth_last_child_element(2)
+            .and_then(|el| el.boolean);
+        match condition_option {
+            Some(condition) => {
+                let children = self
+                    .output_arena_node_parent_id
+                    .children(&self.output_arena)
+                    .count();
+                //remove the value not selected, then the condition
+                let unselected = if condition {
+                    children - 1
+                } else {
+                    children - 2
+                };
+                self.output_arena_remove_child_at(unselected)
+                    .output_arena_remove_child_at(children - 3)
+            }
+            None => {
+                original_self.success = false;
+                original_self.record_error("fn_if - condition isn't a bool");
+                original_self
+            }
+        }
+    }
+
+    ///the guard for functions which can nest themselves, e.g. "neg neg 5" or "+ 1 + 2 3"<br />
+    ///fails with e.g. "fn_negate - max depth exceeded" and returns true if already nested max_depth deep,
+    ///and clears max_depth_exceeded at the top level, so it only says why the current parse failed
+    fn max_depth_reached(self: &mut Parser, name: &str) -> bool {
+        if self.depth == 0 {
+            self.max_depth_exceeded = false;
+        }
+        if self.depth >= self.max_depth {
+            self.fail_max_depth_exceeded(name);
+            true
+        } else {
+            false
+        }
+    }
+
+    ///fails with e.g. "fn_negate - max depth exceeded", at each level on the way back out,
+    ///so the reason is passed on rather than a general fail
+    fn fail_max_depth_exceeded(self: &mut Parser, name: &str) {
+        self.success = false;
+        self.max_depth_exceeded = true;
+        self.record_error(format!("{} - max depth exceeded", name).as_str());
+    }
+
+    ///neg, value (an int, a float or another neg), e.g. "neg 5" (-5) or "neg -3.0" (3.0)<br />
+    ///appends the value, negated in place, and fails if it can't be, e.g. a string, a bool or an integer overflow
+    pub fn fn_negate(mut self: Parser) -> Parser {
+        if self.max_depth_reached("fn_negate") {
+            return self;
+        }
+        let mut original_self = self.clone();
+        self.depth += 1;
+        self = self.prim_word("neg ").chomp_clear().combi_first_success_of(
+            &[
+                Parser::fn_negate,
+                Parser::el_float,
+                Parser::el_int,
+                Parser::el_str,
+                Parser::el_bool,
+            ]
+            .to_vec(),
+        );
+        self.depth -= 1;
+        if !self.success {
+            if self.max_depth_exceeded {
+                original_self.fail_max_depth_exceeded("fn_negate");
+                return original_self;
+            }
+            return original_self.fail_passing_on(&self, "fn_negate");
+        }
+        let el_option = self
+            .output_arena_get_nth_last_child_id(0)
+            .and_then(|node_id| self.output_arena.get(node_id))
+            .map(|node| node.get().clone());
+        let negated = match el_option {
+            Some(mut el) => match el.el_type {
+                Some(ParserElementType::Int64) => {
+                    match el.int64.and_then(|val| val.checked_neg()) {
+                        Some(val) => {
+                            el.int64 = Some(val);
+                            Ok(el)
+                        }
+                        None => Err("fn_negate - integer overflow"),
+                    }
+                }
+                Some(ParserElementType::Float64) => {
+                    el.float64 = el.float64.map(|val| -val);
+                    Ok(el)
+                }
+                _ => Err("fn_negate - cannot negate"),
+            },
+            None => Err("fn_negate - cannot negate"),
+        };
+        match negated {
+            Ok(el) => self.map_last_element(|_| el.clone()).chomp_clear(),
+            Err(error) => {
+                original_self.success = false;
+                original_self.record_error(error);
+                original_self
+            }
+        }
+    }
+
+    ///plus sign, value, value (both ints or both floats), e.g. "+ 1 2" (1 + 2 = 3) or "+ 1.2 3.4" (1.2 + 3.4 = 4.6)<br />
+    ///appends a Sum element with the two values as its children, i.e. it isn't added up until [evaluate](#method.evaluate)<br />
+    ///the values can be sums themselves, e.g. "(+ (+ 1 2) 3)", but only nested up to the parser's max_depth (see [with_max_depth](#method.with_max_depth))
+    pub fn fn_var_sum(self: Parser) -> Parser {
+        self.fn_binary_op_element("fn_var_sum", "sum", "+", ParserElement::new_sum())
+    }
+
+    ///minus sign, value, value (both ints or both floats), e.g. "- 3 1" (3 - 1 = 2)<br />
+    ///works like [fn_var_sum](#method.fn_var_sum), but appends a BinaryOp element which subtracts the second value from the first
+    pub fn fn_var_sub(self: Parser) -> Parser {
+        let el = ParserElement::new_binary_op("-", ParserElement::checked_sub);
+        self.fn_binary_op_element("fn_var_sub", "subtract", "-", el)
+    }
+
+    ///multiply sign, value, value (both ints or both floats), e.g. "* 2 3" (2 * 3 = 6)<br />
+    ///works like [fn_var_sum](#method.fn_var_sum), but appends a BinaryOp element which multiplies the values
+    pub fn fn_var_mul(self: Parser) -> Parser {
+        let el = ParserElement::new_binary_op("*", ParserElement::checked_mul);
+        self.fn_binary_op_element("fn_var_mul", "multiply", "*", el)
+    }
+
+    ///divide sign, value, value (both ints or both floats), e.g. "/ 6 3" (6 / 3 = 2)<br />
+    ///works like [fn_var_sum](#method.fn_var_sum), but appends a BinaryOp element which divides the first value by the second<br />
+    ///an Int64 division by zero parses, but fails when it is evaluated
+    pub fn fn_var_div(self: Parser) -> Parser {
+        let mut el = ParserElement::new_binary_op("/", ParserElement::checked_div);
+        el.reducer_error = Some("division by zero or integer overflow".to_string());
+        self.fn_binary_op_element("fn_var_div", "divide", "/", el)
+    }
+
+    ///symbol, value, value (both ints or both floats), with or without brackets, e.g. "% 7 2" or "(% 7 2)"<br />
+    ///appends a BinaryOp element with the two values as its children, which are combined by the reducer when [evaluate](#method.evaluate) is called<br />
+    ///the reducer returns None if it can't combine the values, e.g. an integer overflow, which fails the evaluate
+    pub fn fn_binary_op(self: Parser, symbol: &str, reducer: ParserElementReducer) -> Parser {
+        let el = ParserElement::new_binary_op(symbol, reducer);
+        self.fn_binary_op_element("fn_binary_op", "combine", symbol, el)
+    }
+
+    ///the shared structure of [fn_binary_op](#method.fn_binary_op) and the arithmetic functions,
+    ///where name and verb are only used in the error messages, e.g. "fn_var_sum - can't sum strings"
+    fn fn_binary_op_element(
+        mut self: Parser,
+        name: &str,
+        verb: &str,
+        symbol: &str,
+        el: ParserElement,
+    ) -> Parser {
+        if self.max_depth_reached(name) {
+            return self;
+        }
+        let mut original_self = self.clone();
+        self.depth += 1;
+        let value = |s: Parser| {
+            Parser::combi_first_success_of(
+                s,
+                &[
+                    Parser::fn_var_sum,
+                    Parser::fn_var_sub,
+                    Parser::fn_var_mul,
+                    Parser::fn_var_div,
+                    Parser::fn_negate,
+                    Parser::el_float,
+                    Parser::el_int,
+                ]
+                .to_vec(),
+            )
+        };
+        //each value can have any amount of spaces or tabs before it, e.g. "+   1   2",
+        //but at least one between them, so "+ 1-2" isn't read as "+ 1 -2".
+        //spaces after the last value are left for the caller, so a nested "+ + 1 2 3" still has its separator
+        let without_brackets = self
+            .clone()
+            .prim_word(format!("{} ", symbol).as_str())
+            .chomp_clear()
+            .prim_spaces_or_tabs()
+            .and_then(value)
+            .prim_spaces_or_tabs_separator()
+            .chomp_clear()
+            .and_then(value);
+
+        let with_brackets = self
+            .clone()
+            .prim_word(format!("({} ", symbol).as_str())
+            .chomp_clear()
+            .prim_spaces_or_tabs()
+            .and_then(value)
+            .prim_spaces_or_tabs_separator()
+            .chomp_clear()
+            .combi_token(value)
+            .prim_word(")");
+
+        if without_brackets.success {
+            self = without_brackets;
+        } else if with_brackets.success {
+            self = with_brackets;
+        } else if without_brackets.max_depth_exceeded || with_brackets.max_depth_exceeded {
+            original_self.fail_max_depth_exceeded(name);
+            return original_self;
+        } else {
+            //pass on the error from whichever form got further
+            let failed = if with_brackets.error_position > without_brackets.error_position {
+                with_brackets
+            } else {
+                without_brackets
+            };
+            return original_self.fail_passing_on(&failed, name);
+        }
+        self.depth -= 1;
+
+        //check both values exist, and are the same type of number
+        let variable2_id_option = self.output_arena_get_nth_last_child_id(0);
+        let variable1_id_option = self.output_arena_get_nth_last_child_id(1);
+        match (variable1_id_option, variable2_id_option) {
+            (Some(variable1_id), Some(variable2_id)) => {
+                match (
+                    self.output_arena_value_type(variable1_id),
+                    self.output_arena_value_type(variable2_id),
+                ) {
+                    (Some(el1_type), Some(el2_type)) if el1_type == el2_type => match el1_type {
+                        ParserElementType::Int64 | ParserElementType::Float64 => {
+                            //keep both values as the children of the new element,
+                            //so the structure is kept until they are combined by evaluate
+                            self = self.output_arena_append_element(el);
+                            if let Some(op_id) = self.output_arena_get_nth_last_child_id(0) {
+                                self.arenas_before_edit(&[variable1_id, variable2_id], &[]);
+                                let arena = &mut self.output_arena;
+                                variable1_id.detach(arena);
+                                op_id.append(variable1_id, arena);
+                                variable2_id.detach(arena);
+                                op_id.append(variable2_id, arena);
+                            }
+                            self = self.chomp_clear();
+                            self
+                        }
+
+                        //can't sum strings
+                        ParserElementType::Str => {
+                            original_self.success = false;
+                            original_self.record_error(
+                                format!("{} - can't {} strings", name, verb).as_str(),
+                            );
+                            original_self
+                        }
+
+                        _ => {
+                            original_self.success = false;
+                            original_self.record_error(
+                                format!("{} - can only {} Int64 or Float64 values", name, verb)
+                                    .as_str(),
+                            );
+                            original_self
+                        }
+                    },
+                    (_, _) => {
+                        original_self.success = false;
+                        original_self.record_error(
+                            format!("{} - can't {} different types", name, verb).as_str(),
+                        );
+                        original_self
+                    }
+                }
+            }
+            _ => {
+                original_self
+                    .record_error(format!("{} - can't find either or both values", name).as_str());
+                original_self.success = false;
+                original_self
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    ///TODO Need a way to test equlity of expected_result
+    fn test_lang_one_of_all_lang_parsers() {
+        let language_string = ">";
+        let _expected_result = (
+            ParserFunctionType::TakesParser(Parser::prim_next),
+            ParserFunctionParam::None,
+        );
+        let p = Parser::new(language_string);
+        let result = Parser::lang_one_of_all_lang_parsers(p);
+        assert_eq!(result.input_original, language_string);
+    }
+
+    #[test]
+    fn test_consumed() {
+        let parser = Parser::new("= x 5");
+        assert_eq!(parser.consumed(), "");
+
+        let result = parser.clone().prim_word("= ").chomp_clear().el_var();
+        assert_eq!(result.consumed(), "= x ");
+        assert_eq!(result.input_remaining(), "5");
+        assert_eq!(result.chomp, "");
+
+        let result = result.el_int();
+        assert_eq!(result.consumed(), "= x 5");
+        assert_eq!(result.input_remaining(), "");
+    }
+
+    #[test]
+    fn test_remaining_graphemes() {
+        let parser = Parser::new("é");
+        assert_eq!(parser.remaining_graphemes(), 1);
+        assert_eq!(parser.input_remaining().len(), 2);
+
+        let parser = Parser::new("ab").prim_char();
+        assert_eq!(parser.remaining_graphemes(), 1);
+    }
+
+    #[test]
+    fn test_and_then() {
+        let mut parser = Parser::new("1a");
+        parser.display_errors = false;
+        let result = parser.clone().prim_digit().and_then(Parser::prim_char);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "1a");
+        assert_eq!(result.success, true);
+
+        //a failure short-circuits f
+        let ran = std::cell::Cell::new(false);
+        let result = parser.clone().prim_eof().and_then(|s: Parser| {
+            ran.set(true);
+            s.prim_char()
+        });
+        assert_eq!(ran.get(), false);
+        assert_eq!(result.input_remaining(), "1a");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_starts_with() {
+        let parser = Parser::new("= x 1");
+        assert_eq!(parser.starts_with("= "), true);
+        assert_eq!(parser.input_remaining(), "= x 1");
+        let parser = Parser::new("x = 1");
+        assert_eq!(parser.starts_with("= "), false);
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let mut parser = Parser::new("123Test");
+        parser.display_errors = false;
+        let mut result = parser.clone().prim_digit();
+        let snap = result.snapshot();
+        result = result.prim_digit().prim_digit().prim_word("Nope");
+        assert_eq!(result.input_remaining(), "Test");
+        assert_eq!(result.chomp, "123");
+        assert_eq!(result.success, false);
+
+        result = result.restore(snap);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "23Test");
+        assert_eq!(result.chomp, "1");
+        assert_eq!(result.chomping, true);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_parser_element_constructors() {
+        let el = ParserElement::new_int(-1);
+        assert_eq!(el.el_type, Some(ParserElementType::Int64));
+        assert_eq!(el.int64, Some(-1));
+        assert_eq!(el.float64, None);
+        assert_eq!(el.string, None);
+        assert_eq!(el.var_name, None);
+
+        let el = ParserElement::new_float(1.5);
+        assert_eq!(el.el_type, Some(ParserElementType::Float64));
+        assert_eq!(el.int64, None);
+        assert_eq!(el.float64, Some(1.5));
+        assert_eq!(el.string, None);
+        assert_eq!(el.var_name, None);
+
+        let el = ParserElement::new_str("test".to_string());
+        assert_eq!(el.el_type, Some(ParserElementType::Str));
+        assert_eq!(el.int64, None);
+        assert_eq!(el.float64, None);
+        assert_eq!(el.string, Some("test".to_string()));
+        assert_eq!(el.var_name, None);
+
+        let el = ParserElement::new_var("x".to_string(), ParserElement::new_int(5));
+        assert_eq!(el.el_type, Some(ParserElementType::Var));
+        assert_eq!(el.int64, Some(5));
+        assert_eq!(el.float64, None);
+        assert_eq!(el.string, None);
+        assert_eq!(el.var_name, Some("x".to_string()));
+    }
+
+    #[test]
+    fn test_parser_el_from_parser_element() {
+        let el = ParserEl::from(ParserElement::new_int(-1));
+        assert_eq!(el, ParserEl::new_int(-1));
+        assert_eq!(el.el_type(), Some(&ParserElementType::Int64));
+        assert_eq!(el.value(), Some(&ParserElValue::I64(-1)));
+        assert_eq!(el.var_name(), None);
+
+        let el = ParserEl::from(ParserElement::new_float(1.5));
+        assert_eq!(el, ParserEl::new_float(1.5));
+        assert_eq!(el.el_type(), Some(&ParserElementType::Float64));
+        assert_eq!(el.value(), Some(&ParserElValue::F64(1.5)));
+
+        let el = ParserEl::from(ParserElement::new_str("test".to_string()));
+        assert_eq!(el, ParserEl::new_str("test".to_string()));
+        assert_eq!(el.el_type(), Some(&ParserElementType::Str));
+        assert_eq!(el.value(), Some(&ParserElValue::Str("test".to_string())));
+
+        //unassigned variable
+        let el = ParserEl::from(ParserElement::new_var(
+            "x".to_string(),
+            ParserElement::new(),
+        ));
+        assert_eq!(el, ParserEl::new_var("x".to_string(), None));
+        assert_eq!(el.el_type(), Some(&ParserElementType::Var));
+        assert_eq!(el.value(), Some(&ParserElValue::Var("x".to_string())));
+        assert_eq!(el.var_name(), Some("x"));
+
+        //assigned variable
+        let result = Parser::new_and_parse("= x 5", Parser::fn_var_assign);
+        let els = result.output_arena_els();
+        assert_eq!(els.len(), 1);
+        assert_eq!(
+            els[0],
+            ParserEl::new_var("x".to_string(), Some(ParserElValue::I64(5)))
+        );
+    }
+
+    #[test]
+    fn test_clear_error() {
+        let mut parser = Parser::new("abc");
+        parser.display_errors = false;
+        let result = parser.clone().el_int();
+        assert_eq!(result.last_error(), Some("el_int"));
+        assert_eq!(result.success, false);
+
+        //everything after a fail does nothing
+        let failed = result.clone().prim_char();
+        assert_eq!(failed.input_remaining(), "abc");
+        assert_eq!(failed.success, false);
+
+        let result = result.clear_error();
+        assert_eq!(result.input_remaining(), "abc");
+        assert_eq!(result.last_error(), None);
+        assert_eq!(result.last_error_position(), None);
+        assert_eq!(result.success, true);
+
+        let result = result.prim_char();
+        assert_eq!(result.input_remaining(), "bc");
+        assert_eq!(result.chomp, "a");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_capture_errors() {
+        //displayed errors are collected rather than printed
+        let parser = Parser::new("= x 1\r\nabc").capture_errors();
+        let result = parser.clone().prim_word("= x 1\r\n").el_int();
+        assert_eq!(result.success, false);
+        assert_eq!(result.captured_errors(), vec!["el_int at position:7"]);
+
+        //and shared by the clones, so the original sees them too
+        let result = result.clear_error().el_bool();
+        assert_eq!(
+            parser.captured_errors(),
+            vec!["el_int at position:7", "el_bool at position:7"]
+        );
+        assert_eq!(result.captured_errors(), parser.captured_errors());
+
+        //errors which aren't displayed aren't collected either
+        let mut parser = Parser::new("abc").capture_errors();
+        parser.display_errors = false;
+        let result = parser.el_int();
+        assert_eq!(result.last_error(), Some("el_int"));
+        assert_eq!(result.captured_errors(), Vec::<String>::new());
+
+        //nothing is collected without capture_errors
+        let mut parser = Parser::new("abc");
+        parser.display_errors = false;
+        assert_eq!(parser.el_int().captured_errors(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut parser = Parser::new("= x 1\r\n= y 2.5\r\nprint x");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        let summary = result.summary();
+        assert_eq!(summary.element_count(), 3);
+        assert_eq!(summary.success(), true);
+        assert_eq!(summary.bytes_consumed(), parser.input_original.len());
+        assert_eq!(summary.error(), None);
+
+        //stops at the failed statement
+        parser = Parser::new("= x 1\r\n= y\r\nprint x");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        let summary = result.summary();
+        assert_eq!(summary.element_count(), 1);
+        assert_eq!(summary.success(), false);
+        assert_eq!(summary.bytes_consumed(), 7);
+        assert_eq!(summary.error(), result.last_error());
+        assert_eq!(summary.error().is_some(), true);
+
+        //nothing parsed yet
+        assert_eq!(
+            Parser::new("= x 1").summary(),
+            ParseSummary {
+                element_count: 0,
+                success: true,
+                bytes_consumed: 0,
+                error: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_element() {
+        //the built-in types
+        let result = Parser::new("-5").element::<i64>();
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(-5)]);
+        let result = Parser::new("1.5").element::<f64>();
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_float(1.5)]);
+        let result = Parser::new("\"a\"").element::<String>();
+        assert_eq!(
+            result.output_arena_els(),
+            vec![ParserEl::new_str("a".to_string())]
+        );
+        let result = Parser::new("'a'").element::<char>();
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_char('a')]);
+        let result = Parser::new("true").element::<bool>();
+        assert_eq!(result.success, true);
+        let mut parser = Parser::new("x");
+        parser.display_errors = false;
+        let result = parser.element::<i64>();
+        assert_eq!(result.last_error(), Some("el_int"));
+        assert_eq!(result.success, false);
+
+        //a user type, e.g. a percentage like "50%", stored as an Int64
+        struct Percent;
+        impl Parseable for Percent {
+            fn parse(p: Parser) -> Parser {
+                p.el_uint().prim_word("%").chomp_clear()
+            }
+        }
+        let mut parser = Parser::new("50% off");
+        parser.display_errors = false;
+        let result = parser.clone().element::<Percent>();
+        assert_eq!(result.input_remaining(), " off");
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(50)]);
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //and mixed with the rest, e.g. as one of the alternatives
+        parser = Parser::new("50%");
+        parser.display_errors = false;
+        let result = parser.clone().combi_first_success_of(
+            &[Parser::element::<bool>, Parser::element::<Percent>].to_vec(),
+        );
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(50)]);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_collect_as() {
+        #[derive(Debug, PartialEq)]
+        struct Number(i64);
+
+        impl TryFrom<ParserElement> for Number {
+            type Error = String;
+            fn try_from(el: ParserElement) -> Result<Number, String> {
+                match ParserEl::from(el).value() {
+                    Some(ParserElValue::I64(val)) => Ok(Number(*val)),
+                    value => Err(format!("not a number: {:?}", value)),
+                }
+            }
+        }
+
+        let mut parser = Parser::new("");
+        parser = parser
+            .output_arena_append_element(ParserElement::new_int(1))
+            .output_arena_append_element(ParserElement::new_int(2));
+        assert_eq!(
+            parser.collect_as::<Number>(),
+            Ok(vec![Number(1), Number(2)])
+        );
+
+        //from parsed input
+        let result = Parser::new_and_parse("-42", Parser::el_int);
+        assert_eq!(result.collect_as::<Number>(), Ok(vec![Number(-42)]));
+
+        //no elements
+        assert_eq!(Parser::new("").collect_as::<Number>(), Ok(vec![]));
+
+        //fails on any element which isn't a Number
+        parser = parser.output_arena_append_element(ParserElement::new_str("3".to_string()));
+        assert_eq!(
+            parser.collect_as::<Number>(),
+            Err("not a number: Some(Str(\"3\"))".to_string())
+        );
+    }
+
+    #[test]
+    fn test_output_arena_child_at() {
+        let mut parser = Parser::new("");
+        parser = parser
+            .output_arena_append_element(ParserElement::new_int(1))
+            .output_arena_append_element(ParserElement::new_int(2))
+            .output_arena_append_element(ParserElement::new_int(3));
+        assert_eq!(parser.output_arena_get_child_at(1).unwrap().int64, Some(2));
+
+        parser = parser.output_arena_remove_child_at(1);
+        assert_eq!(parser.output_arena_get_child_at(0).unwrap().int64, Some(1));
+        assert_eq!(parser.output_arena_get_child_at(1).unwrap().int64, Some(3));
+        assert_eq!(parser.output_arena_get_child_at(2).is_none(), true);
+
+        //out of range does nothing
+        parser = parser.output_arena_remove_child_at(5);
+        assert_eq!(parser.output_arena_get_child_at(1).unwrap().int64, Some(3));
+    }
+
+    #[test]
+    fn test_new_validating() {
+        let input_str = "12, 34";
+        let validate = |s: Parser| {
+            s.combi_separated_by(Parser::el_int, |s: Parser| s.prim_word(", ").chomp_clear())
+                .prim_eof()
+        };
+        let mut parser = Parser::new(input_str);
+        parser.display_errors = false;
+        let built = validate(parser);
+        assert_eq!(built.output_arena_get_child_at(1).unwrap().int64, Some(34));
+
+        parser = Parser::new_validating(input_str);
+        parser.display_errors = false;
+        let validated = validate(parser);
+        assert_eq!(validated.input_remaining(), built.input_remaining());
+        assert_eq!(validated.success, built.success);
+        assert_eq!(validated.output_arena_get_child_at(0).is_none(), true);
+        assert_eq!(validated.output_arena.iter().count(), 1);
+
+        parser = Parser::new_validating("12, x");
+        parser.display_errors = false;
+        let validated = validate(parser);
+        assert_eq!(validated.success, false);
+    }
+
+    #[test]
+    fn test_output_arena_clear() {
+        let mut parser = Parser::new("= x 1\r\n= y 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign().output_arena_clear();
+        assert_eq!(result.input_remaining(), "= y 2");
+        assert_eq!(result.output_arena_get_child_at(0).is_none(), true);
+
+        let result = result.fn_var_assign();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(
+            result.output_arena_get_child_at(0).unwrap().var_name,
+            Some("y".to_string())
+        );
+        assert_eq!(result.output_arena_get_child_at(1).is_none(), true);
+        //only the root and y are left
+        let count = result
+            .output_arena
+            .iter()
+            .filter(|n| !n.is_removed())
+            .count();
+        assert_eq!(count, 2);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_map_last_element() {
+        let mut parser = Parser::new("21");
+        parser.display_errors = false;
+        parser = parser.el_int();
+        let count = parser.output_arena.iter().count();
+        let result = parser.map_last_element(|mut el| {
+            el.int64 = el.int64.map(|val| val * 2);
+            el
+        });
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(42));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.output_arena.iter().count(), count);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_set_last_element_type() {
+        let mut parser = Parser::new("21");
+        parser.display_errors = false;
+        let result = parser
+            .el_int()
+            .set_last_element_type(ParserElementType::Print);
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Print));
+                assert_eq!(el.int64, Some(21));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //no last element
+        let result = Parser::new("").set_last_element_type(ParserElementType::Print);
+        assert_eq!(result.output_arena_els(), vec![]);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_get_parser_function_by_name() {
+        assert_eq!(
+            Parser::get_parser_function_by_name(">".to_string()) == Some(Parser::lang_prim_next),
+            true
+        );
+        assert_eq!(
+            Parser::get_parser_function_by_name("'".to_string()) == Some(Parser::lang_prim_word),
+            true
+        );
+        //unknown aliases aren't silently treated as eof
+        assert_eq!(
+            Parser::get_parser_function_by_name("?".to_string()).is_none(),
+            true
+        );
+    }
+    //================================================================================
+    //Start Language Aliases Testing
+    //================================================================================
+
+    //lang_elements
+
+    #[test]
+    fn test_lang_el() {
+        let mut input_str = "1234";
+        let mut language_string = "$int";
+        let mut result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        let mut el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(1234));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        input_str = "-12.5";
+        language_string = "$flt";
+        result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_remaining(), "");
+        el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => assert_eq!(el.float64, Some(-12.5)),
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        input_str = "\"test\"";
+        language_string = "$str";
+        result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_remaining(), "");
+        el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => assert_eq!(el.string, Some("test".to_string())),
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        assert_eq!(
+            Parser::get_parser_function_by_name("$var".to_string()) == Some(Parser::lang_el_var),
+            true
+        );
+    }
+
+    //lang_combinators
+
+    #[test]
+    fn test_lang_combi_one_or_more() {
+        //only combis of prims so far
+        let mut input_str = "aaaa";
+        let mut language_string = "1+@";
+        let mut result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "aaaa");
+        assert_eq!(result.success, true);
+
+        input_str = "123abc";
+        language_string = "1+#";
+        result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "abc");
+        assert_eq!(result.chomp, "123");
+        assert_eq!(result.success, true);
+    }
+
+    fn prim_vowel(self_: Parser) -> Parser {
+        self_.combi_first_success_of(
+            &[
+                |p: Parser| p.prim_word("a"),
+                |p: Parser| p.prim_word("e"),
+                |p: Parser| p.prim_word("i"),
+                |p: Parser| p.prim_word("o"),
+                |p: Parser| p.prim_word("u"),
+            ]
+            .to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_lang_custom_parser() {
+        let mut custom_parsers: HashMap<String, ParserFunction> = HashMap::new();
+        custom_parsers.insert("vowel".to_string(), prim_vowel);
+        let mut input_str = "e1x";
+        let language_string = "vowel#@";
+        let mut result = Parser::new_and_parse_aliases_with_custom_parsers(
+            input_str,
+            language_string,
+            &custom_parsers,
+        );
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.success, true);
+
+        input_str = "x1x";
+        result = Parser::new_and_parse_aliases_with_custom_parsers(
+            input_str,
+            language_string,
+            &custom_parsers,
+        );
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.success, false);
+
+        //without the custom parser, the name isn't an alias
+        let mut parser_lang = Parser::new("vowel");
+        parser_lang.display_errors = false;
+        parser_lang = parser_lang.lang_custom_parser();
+        assert_eq!(parser_lang.success, false);
+    }
+
+    #[test]
+    fn test_lang_combi_optional() {
+        let mut input_str = "1x";
+        let language_string = "??#@";
+        let mut result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "1x");
+        assert_eq!(result.success, true);
+
+        input_str = "xy";
+        result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "y");
+        assert_eq!(result.chomp, "x");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_lang_combi_first_success_of() {
+        let mut input_str = "1x";
+        let mut language_string = "[# @]";
+        let mut result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "x");
+        assert_eq!(result.chomp, "1");
+        assert_eq!(result.success, true);
+
+        input_str = "x1";
+        language_string = "[# @]#";
+        result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "x1");
+        assert_eq!(result.success, true);
+
+        //unclosed
+        let mut parser = Parser::new("[# @");
+        parser.display_errors = false;
+        let result = parser.clone().lang_combi_first_success_of();
+        assert_eq!(result.input_remaining(), "[# @");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_lang_prim_word() {
+        let input_str = "test";
+        let language_string = "'test'";
+        let result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        //assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "test");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_lang_prim_eols_or_eof() {
+        let mut input_str = "\r\n\r\n\r\n!\n";
+        let mut language_string = ",@,";
+        let mut result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\r\n\r\n\r\n!\n");
+        assert_eq!(result.success, true);
+
+        input_str = "a";
+        language_string = "@.";
+        result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "a");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_lang_prim_eof() {
+        let input_str = "a";
+        let language_string = "@.";
+        let result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "a");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_lang_prim_eols() {
+        let input_str = "\r\n\r\n\r\n!\n";
+        let language_string = ",@,";
+        let result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.chomp, "\r\n\r\n\r\n!\n");
         assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_lang_prim_digit() {
+        let input_str = "0123456789";
+        let language_string = "##########";
+        let result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "0123456789");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_lang_prim_char() {
+        let input_str = "+%!";
+        let language_string = "@@@";
+        let result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "+%!");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_lang_prim_quote() {
+        let input_str = "\"\"\"";
+        let language_string = "\"\"\"";
+        let result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_lang_prim_next() {
+        let input_str = "123";
+        let language_string = ">>>";
+        let result = Parser::new_and_parse_aliases(input_str, language_string);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "123");
+        assert_eq!(result.success, true);
+    }
+    //================================================================================
+    //End Language Aliases Testing
+    //================================================================================
+
+    #[test]
+    //A string
+    fn test_el_string() {
+        let input_str = "\"1234\"";
+        let result = Parser::new_and_parse(input_str, Parser::el_str);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.output_arena.count(), 2);
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Str));
+                assert_eq!(el.string, Some("1234".to_string()));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_el_string_escaped() {
+        let escapes = [
+            ("\"line1\\nline2\"", "line1\nline2"),
+            ("\"tab\\ttab\"", "tab\ttab"),
+            ("\"cr\\rcr\"", "cr\rcr"),
+            ("\"say \\\"hi\\\"\"", "say \"hi\""),
+            ("\"back\\\\slash\"", "back\\slash"),
+        ];
+        for (input_str, expected) in escapes.iter() {
+            let result = Parser::new_and_parse(input_str, Parser::el_str);
+            assert_eq!(result.input_original, *input_str);
+            assert_eq!(result.input_remaining(), "");
+            let el_option = result.clone().output_arena_get_last_child_element();
+            match el_option {
+                Some(el) => {
+                    assert_eq!(el.el_type, Some(ParserElementType::Str));
+                    assert_eq!(el.string, Some(expected.to_string()));
+                }
+                _ => assert!(true, false),
+            }
+            assert_eq!(result.chomp, "");
+            assert_eq!(result.success, true);
+        }
+
+        //unknown escape
+        let mut parser = Parser::new("\"bad\\q\"");
+        parser.display_errors = false;
+        let result = parser.clone().el_str();
+        assert_eq!(
+            result
+                .clone()
+                .output_arena_get_last_child_element()
+                .is_none(),
+            true
+        );
+        assert_eq!(result.success, false);
+
+        //dangling backslash at eof
+        let mut parser = Parser::new("\"dangling\\");
+        parser.display_errors = false;
+        let result = parser.clone().el_str();
+        assert_eq!(
+            result
+                .clone()
+                .output_arena_get_last_child_element()
+                .is_none(),
+            true
+        );
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    //Next
+    fn test_prim_next() {
+        //Fails
+        let input_str = "";
+        let result = Parser::new_and_parse(input_str, Parser::prim_next);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //character alpha
+        let input_str = "abc";
+        let result = Parser::new_and_parse(input_str, Parser::prim_next);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "bc");
+        assert_eq!(result.chomp, "a");
+        assert_eq!(result.success, true);
+
+        //character number
+        let input_str = "1bc";
+        let result = Parser::new_and_parse(input_str, Parser::prim_next);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "bc");
+        assert_eq!(result.chomp, "1");
+        assert_eq!(result.success, true);
+
+        //character special
+        let input_str = "~bc";
+        let result = Parser::new_and_parse(input_str, Parser::prim_next);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "bc");
+        assert_eq!(result.chomp, "~");
+        assert_eq!(result.success, true);
+
+        //character backslash
+        let input_str = "\\bc";
+        let result = Parser::new_and_parse(input_str, Parser::prim_next);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "bc");
+        assert_eq!(result.chomp, "\\");
+        assert_eq!(result.success, true);
+
+        //character unicode
+        let input_str = "ébc";
+        let result = Parser::new_and_parse(input_str, Parser::prim_next);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "bc");
+        assert_eq!(result.chomp, "é");
+        assert_eq!(result.success, true);
+    }
+
+    //= x + 1 2
+    //print x
+    //= y + 1.1 2.2
+    //print y
+    #[test]
+    fn test_run2() {
+        let func = |p| Parser::combi_first_success_of(p, &[Parser::fn_var_assign].to_vec());
+        let input_str = "= x 123";
+        let result = Parser::new_and_parse(input_str, func);
+        assert_eq!(result.input_original, input_str);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(
+            result
+                .output_arena
+                .iter()
+                .filter(|n| !n.is_removed())
+                .count(),
+            2
+        );
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(123));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_variable_sum() {
+        //not a valid el_var sum
+        let mut parser = Parser::new(" + test 1");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " + test 1");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //short el_int plus short el_int, with combi_optional brackets
+        parser = Parser::new("(+ 1 2)");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(3));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //short el_int plus short el_int
+        parser = Parser::new("+ 1 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(3));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //long el_int plus long el_int
+        parser = Parser::new("+ 11111 22222");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(33333));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //long el_int plus negative long el_int
+        parser = Parser::new("+ 11111 -22222");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(-11111));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //el_int overflow fails cleanly when evaluated, leaving the output as it was
+        parser = Parser::new("+ 9223372036854775807 1");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => assert_eq!(el.el_type, Some(ParserElementType::Sum)),
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.last_error(), Some("evaluate - integer overflow"));
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //short el_float plus short el_float
+        parser = Parser::new("+ 1.1 2.2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(3.3000000000000003)); // yikes, floats
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //long el_float plus long el_float
+        parser = Parser::new("+ 11111.11111 22222.22222");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(33333.33333)); // yikes, floats
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //long el_float plus negative long el_float
+        parser = Parser::new("+ 11111.11111 -22222.22222");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(-11111.11111)); // yikes, floats
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_fn_if() {
+        let mut parser = Parser::new("if == 1 1 42 0");
+        parser.display_errors = false;
+        let result = parser.clone().fn_if();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.output_arena_get_child_at(1).is_none(), true);
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(42));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("if == 1 2 42 0");
+        parser.display_errors = false;
+        let result = parser.clone().fn_if();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.output_arena_get_child_at(1).is_none(), true);
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(0));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //as a value to assign
+        parser = Parser::new("= x if false 1 + 1 1");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign().evaluate();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.int64, Some(2));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //the condition must be a bool
+        parser = Parser::new("if 1 42 0");
+        parser.display_errors = false;
+        let result = parser.clone().fn_if();
+        assert_eq!(result.input_remaining(), "if 1 42 0");
+        assert_eq!(result.success, false);
+
+        //values must be separated by a space or tab
+        for input in ["if true1 2", "if true 1\"a\"", "== 1-1"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser
+                .clone()
+                .combi_first_success_of(&[Parser::fn_if, Parser::fn_equals].to_vec());
+            assert_eq!(result.input_remaining(), *input);
+            assert_eq!(result.output_arena_get_child_at(0).is_none(), true);
+            assert_eq!(result.success, false);
+        }
+
+        //comparing different types
+        parser = Parser::new("== 1 \"1\"");
+        parser.display_errors = false;
+        let result = parser.clone().fn_equals();
+        assert_eq!(
+            result.last_error(),
+            Some("fn_equals - can't compare different types")
+        );
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_fn_negate() {
+        let mut parser = Parser::new("neg 5");
+        parser.display_errors = false;
+        let result = parser.clone().fn_negate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(-5));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("neg -3.0");
+        parser.display_errors = false;
+        let result = parser.clone().fn_negate();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(3.0));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //as a value to assign
+        parser = Parser::new("= x neg neg 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.int64, Some(2));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //overflow
+        parser = Parser::new("neg -9223372036854775808");
+        parser.display_errors = false;
+        let result = parser.clone().fn_negate();
+        assert_eq!(result.input_remaining(), "neg -9223372036854775808");
+        assert_eq!(result.last_error(), Some("fn_negate - integer overflow"));
+        assert_eq!(result.success, false);
+
+        //can't negate a string or a bool
+        for input in ["neg \"a\"", "neg true"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().fn_negate();
+            assert_eq!(result.input_remaining(), *input);
+            assert_eq!(result.last_error(), Some("fn_negate - cannot negate"));
+            assert_eq!(result.success, false);
+        }
+
+        //nesting is limited by max_depth, like fn_var_sum
+        parser = Parser::with_max_depth("neg neg 5", 2);
+        parser.display_errors = false;
+        let result = parser.clone().fn_negate();
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(5)]);
+        assert_eq!(result.success, true);
+        parser = Parser::with_max_depth("neg neg neg 5", 2);
+        parser.display_errors = false;
+        let result = parser.clone().fn_negate();
+        assert_eq!(result.input_remaining(), "neg neg neg 5");
+        assert_eq!(result.last_error(), Some("fn_negate - max depth exceeded"));
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_fn_var_sum_max_depth() {
+        let nested = |levels: usize| "(+ ".repeat(levels) + "1 1" + &") 1".repeat(levels - 1) + ")";
+        let mut parser = Parser::with_max_depth(nested(3).as_str(), 3);
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.int64, Some(4));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        parser = Parser::with_max_depth(nested(4).as_str(), 3);
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum();
+        assert_eq!(result.input_remaining(), parser.input_original);
+        assert_eq!(result.last_error(), Some("fn_var_sum - max depth exceeded"));
+        assert_eq!(result.success, false);
+
+        //very deep nesting fails gracefully, rather than overflowing the stack
+        parser = Parser::new(nested(10000).as_str());
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum();
+        assert_eq!(result.last_error(), Some("fn_var_sum - max depth exceeded"));
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_fn_binary_op() {
+        //each arithmetic function, for ints and floats, with or without brackets
+        let tests: Vec<(&str, Parser, ParserEl)> = vec![
+            (
+                "- 5 3",
+                Parser::new("- 5 3").fn_var_sub(),
+                ParserEl::new_int(2),
+            ),
+            (
+                "- 5 -3",
+                Parser::new("- 5 -3").fn_var_sub(),
+                ParserEl::new_int(8),
+            ),
+            (
+                "(- 1.5 0.5)",
+                Parser::new("(- 1.5 0.5)").fn_var_sub(),
+                ParserEl::new_float(1.0),
+            ),
+            (
+                "* 2 3",
+                Parser::new("* 2 3").fn_var_mul(),
+                ParserEl::new_int(6),
+            ),
+            (
+                "(* 1.5 2.0)",
+                Parser::new("(* 1.5 2.0)").fn_var_mul(),
+                ParserEl::new_float(3.0),
+            ),
+            (
+                "/ 7 2",
+                Parser::new("/ 7 2").fn_var_div(),
+                ParserEl::new_int(3),
+            ),
+            (
+                "(/ 7.0 2.0)",
+                Parser::new("(/ 7.0 2.0)").fn_var_div(),
+                ParserEl::new_float(3.5),
+            ),
+            (
+                "+ 1 2",
+                Parser::new("+ 1 2").fn_var_sum(),
+                ParserEl::new_int(3),
+            ),
+        ];
+        for (input, result, expected) in tests {
+            let result = result.evaluate();
+            assert_eq!(result.input_remaining(), "", "{}", input);
+            assert_eq!(result.output_arena_els(), vec![expected], "{}", input);
+            assert_eq!(result.chomp, "", "{}", input);
+            assert_eq!(result.success, true, "{}", input);
+        }
+
+        //the operations can be nested inside each other, i.e. (1 + 2) * (5 - 3)
+        let mut parser = Parser::new("* (+ 1 2) (- 5 3)");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_mul();
+        assert_eq!(
+            result.debug_tree(),
+            "Root\n  BinaryOp Str(\"*\")\n    Sum\n      Int64 I64(1)\n      Int64 I64(2)\n    BinaryOp Str(\"-\")\n      Int64 I64(5)\n      Int64 I64(3)\n"
+        );
+        let result = result.evaluate();
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(6)]);
+        assert_eq!(result.success, true);
+
+        //and assigned to a variable
+        parser = Parser::new("= x / 9 3");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign().evaluate();
+        assert_eq!(result.debug_tree(), "Root\n  Var x I64(3)\n");
+        assert_eq!(result.success, true);
+
+        //an integer division by zero parses, but fails when evaluated
+        parser = Parser::new("/ 1 0");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_div().evaluate();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(
+            result.last_error(),
+            Some("evaluate - division by zero or integer overflow")
+        );
+        assert_eq!(result.success, false);
+
+        //integer overflow
+        parser = Parser::new("* 9223372036854775807 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_mul().evaluate();
+        assert_eq!(result.last_error(), Some("evaluate - integer overflow"));
+        assert_eq!(result.success, false);
+
+        //can't mix types
+        parser = Parser::new("* 1 2.5");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_mul();
+        assert_eq!(result.input_remaining(), "* 1 2.5");
+        assert_eq!(
+            result.last_error(),
+            Some("fn_var_mul - can't multiply different types")
+        );
+        assert_eq!(result.success, false);
+
+        //a custom operation, using any reducer
+        let remainder = |el1: &ParserElement, el2: &ParserElement| match (el1.int64, el2.int64) {
+            (Some(val1), Some(val2)) => val1.checked_rem(val2).map(ParserElement::new_int),
+            (_, _) => None,
+        };
+        parser = Parser::new("(% 7 4)");
+        parser.display_errors = false;
+        let result = parser.clone().fn_binary_op("%", remainder).evaluate();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(3)]);
+        assert_eq!(result.success, true);
+
+        //the reducer can refuse values it can't combine
+        parser = Parser::new("% 7.0 4.0");
+        parser.display_errors = false;
+        let result = parser.clone().fn_binary_op("%", remainder).evaluate();
+        assert_eq!(
+            result.last_error(),
+            Some("evaluate - can't combine these values")
+        );
+        assert_eq!(result.success, false);
+
+        //the division message belongs to fn_var_div's element, not to any "/" symbol
+        parser = Parser::new("/ 7 0");
+        parser.display_errors = false;
+        let result = parser.clone().fn_binary_op("/", remainder).evaluate();
+        assert_eq!(result.last_error(), Some("evaluate - integer overflow"));
+        assert_eq!(result.success, false);
+
+        //not the symbol
+        parser = Parser::new("+ 1 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sub();
+        assert_eq!(result.input_remaining(), "+ 1 2");
+        assert_eq!(result.last_error(), Some("fn_var_sub"));
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        //the Sum structure is kept until evaluated, i.e. x = 1 + (2 + 3)
+        let mut parser = Parser::new("= x + 1 + 2 3");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(
+            result.debug_tree(),
+            "Root\n  Var x\n    Sum\n      Int64 I64(1)\n      Sum\n        Int64 I64(2)\n        Int64 I64(3)\n"
+        );
+        assert_eq!(result.success, true);
+
+        //then folded into the value
+        let result = result.evaluate();
+        assert_eq!(result.debug_tree(), "Root\n  Var x I64(6)\n");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(6));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //print evaluates the variable without needing evaluate, and type annotations check the Sum's type
+        parser = Parser::new("= x:float + 1.5 2.5\r\nprint x");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Print));
+                assert_eq!(el.float64, Some(4.0));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //can't sum different types
+        parser = Parser::new("+ 1 2.5");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum();
+        assert_eq!(result.input_remaining(), "+ 1 2.5");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_str_concat() {
+        //not a string concat
+        let mut parser = Parser::new(". 1 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_str_concat();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), ". 1 2");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //string concat string
+        parser = Parser::new(". \"foo\" \"bar\"");
+        parser.display_errors = false;
+        let result = parser.clone().fn_str_concat();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(
+            result
+                .output_arena
+                .iter()
+                .filter(|n| !n.is_removed())
+                .count(),
+            2
+        );
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Str));
+                assert_eq!(el.string, Some("foobar".to_string()));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //string concat nested string concat
+        parser = Parser::new(". \"a\" . \"b\" \"c\"");
+        parser.display_errors = false;
+        let result = parser.clone().fn_str_concat();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Str));
+                assert_eq!(el.string, Some("abc".to_string()));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_has_var() {
+        let mut parser = Parser::new("= x 1\r\n{\r\n= y 2\r\n}\r\n");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.success, true);
+        assert_eq!(result.has_var("x"), true);
+        assert_eq!(result.has_var("z"), false);
+        //y's scope has ended
+        assert_eq!(result.has_var("y"), false);
+        assert_eq!(parser.has_var("x"), false);
+    }
+
+    #[test]
+    fn test_output_arena_find_element_var() {
+        let mut parser = Parser::new("= x 1\r\n= y 2.5\r\n= x 3");
+        parser.display_errors = false;
+        let result = parser.parse();
+        //repeated lookups only borrow the parser
+        for _ in 0..3 {
+            let el_option = result.output_arena_find_element_var("x");
+            match el_option {
+                Some(el) => assert_eq!(el.int64, Some(3)),
+                _ => assert!(true, false),
+            }
+            let el_option = result.output_arena_find_element_var("y");
+            match el_option {
+                Some(el) => assert_eq!(el.float64, Some(2.5)),
+                _ => assert!(true, false),
+            }
+            assert_eq!(result.output_arena_find_element_var("z").is_none(), true);
+        }
+        //so it can still be used afterwards, without having been cloned
+        let result = result.evaluate();
+        assert_eq!(result.output_arena_els().len(), 3);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = std::env::temp_dir().join("rust_learning_parser_combinators_test_from_file.txt");
+        std::fs::write(&path, "= x 1\r\n= y 2.5\r\n").unwrap();
+        let parser = Parser::from_file(&path).unwrap();
+        assert_eq!(parser.input_original, "= x 1\r\n= y 2.5\r\n");
+        let result = parser.parse();
+        assert_eq!(result.input_remaining(), "");
+
+        let mut el_option = result.output_arena_find_element_var("x");
+        match el_option {
+            Some(el) => assert_eq!(el.int64, Some(1)),
+            _ => assert!(true, false),
+        }
+        el_option = result.output_arena_find_element_var("y");
+        match el_option {
+            Some(el) => assert_eq!(el.float64, Some(2.5)),
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        let parser = Parser::from_file_normalizing_eols(&path).unwrap();
+        assert_eq!(parser.input_original, "= x 1\n= y 2.5\n");
+        std::fs::remove_file(&path).unwrap();
+
+        //missing file
+        assert_eq!(Parser::from_file(&path).is_err(), true);
+    }
+
+    #[test]
+    fn test_multiple_variable_assign() {
+        let input_string = "= x + 1 2\r\n= y + 3 4\r\n= z + 5.0 6.0";
+        let mut parser = Parser::new(input_string);
+        //parser.display_errors = false;
+        let result = parser.parse().evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+
+        let mut el_option = result.clone().output_arena_get_nth_last_child_element(2);
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(3));
+            }
+            _ => assert!(true, false),
+        }
+
+        el_option = result.clone().output_arena_get_nth_last_child_element(1);
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("y".to_string()));
+                assert_eq!(el.int64, Some(7));
+            }
+            _ => assert!(true, false),
+        }
+
+        el_option = result.clone().output_arena_get_nth_last_child_element(0);
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("z".to_string()));
+                assert_eq!(el.float64, Some(11.0));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //don't create new var_name if already exists, update it
+        parser = Parser::new("= x + 1 2\r\n= x + 3 4");
+        parser.display_errors = false;
+        let result = parser.clone().parse().evaluate();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(7));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_feed() {
+        let mut parser = Parser::new("");
+        parser.display_errors = false;
+        parser.feed("= x ");
+        let (mut result, needs_more) = parser.parse_fed();
+        assert_eq!(result.input_remaining(), "= x ");
+        assert_eq!(result.output_arena_get_child_at(0).is_none(), true);
+        assert_eq!(needs_more, true);
+        assert_eq!(result.success, true);
+
+        result.feed("5\r\n");
+        let (result, needs_more) = result.parse_fed();
+        assert_eq!(result.input_original, "= x 5\r\n");
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.int64, Some(5));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(needs_more, false);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_new_normalized() {
+        let mut mixed = Parser::new_normalized("= x 1\r\n= y 2\r= z 3\n\r\nprint x");
+        mixed.display_errors = false;
+        let mut uniform = Parser::new("= x 1\n= y 2\n= z 3\n\nprint x");
+        uniform.display_errors = false;
+        assert_eq!(mixed.input_original, uniform.input_original);
+        let mixed_result = mixed.parse();
+        let uniform_result = uniform.parse();
+        assert_eq!(mixed_result.input_remaining(), "");
+        assert_eq!(mixed_result.debug_tree(), uniform_result.debug_tree());
+        assert_eq!(mixed_result.success, true);
+        assert_eq!(uniform_result.success, true);
+    }
+
+    #[test]
+    fn test_scope() {
+        let mut parser = Parser::new("= x 1\r\n{\r\n= x 2\r\n}\r\nprint x");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Print));
+                assert_eq!(el.int64, Some(1));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //inner scopes can see outer variables
+        parser = Parser::new("= x 1\r\n{\r\nprint x\r\n}");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.success, true);
+
+        //no scope to end
+        parser = Parser::new("}");
+        parser.display_errors = false;
+        let result = parser.clone().fn_scope_end();
+        assert_eq!(result.input_remaining(), "}");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_debug_tree() {
+        let mut parser = Parser::new("= x 1\r\n{\r\n= y 2\r\n}");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.success, true);
+        assert_eq!(
+            result.debug_tree(),
+            "Root\n  Var x I64(1)\n  Scope\n    Var y I64(2)\n"
+        );
+    }
+
+    #[test]
+    fn test_prim_comment() {
+        let mut parser = Parser::new("// set x\r\n= x 1");
+        parser.display_errors = false;
+        let result = parser.clone().prim_comment();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "\r\n= x 1");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //full line and inline comments
+        parser = Parser::new("// set x\r\n= x 1 // to 1\r\n// done");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.output_arena_find_element_var("x");
+        match el_option {
+            Some(el) => assert_eq!(el.int64, Some(1)),
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //not a comment
+        parser = Parser::new("/ set x");
+        parser.display_errors = false;
+        let result = parser.clone().prim_comment();
+        assert_eq!(result.input_remaining(), "/ set x");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_prim_block_comment() {
+        let mut parser = Parser::new("/* a */= x 1");
+        parser.display_errors = false;
+        let result = parser.clone().prim_block_comment();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "= x 1");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //nested
+        parser = Parser::new("/* a /* b */ c */\r\n= x 1 /* x */");
+        parser.display_errors = false;
+        let result = parser.clone().prim_block_comment();
+        assert_eq!(result.input_remaining(), "\r\n= x 1 /* x */");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+        let result = parser.clone().parse();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.output_arena_find_element_var("x");
+        match el_option {
+            Some(el) => assert_eq!(el.int64, Some(1)),
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //unterminated
+        parser = Parser::new("/* a /* b */ c");
+        parser.display_errors = false;
+        let result = parser.clone().prim_block_comment();
+        assert_eq!(result.input_remaining(), "/* a /* b */ c");
+        assert_eq!(result.success, false);
+        assert_eq!(
+            result.last_error(),
+            Some("prim_block_comment - unterminated")
+        );
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let mut parser = Parser::new("= x 1\r\n= bad\r\n= y 2");
+        parser.display_errors = false;
+        let (elements, errors) = parser.parse_all();
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0].var_name, Some("x".to_string()));
+        assert_eq!(elements[0].int64, Some(1));
+        assert_eq!(elements[1].var_name, Some("y".to_string()));
+        assert_eq!(elements[1].int64, Some(2));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line(), 2);
+        assert_eq!(errors[0].position(), 7);
+        assert_eq!(errors[0].message().is_empty(), false);
+
+        //every error is collected, including on the last line
+        parser = Parser::new("= bad\n= x 1\n= worse");
+        parser.display_errors = false;
+        let (elements, errors) = parser.parse_all();
+        assert_eq!(elements.len(), 1);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line(), 1);
+        assert_eq!(errors[1].line(), 3);
+    }
+
+    #[test]
+    fn test_print() {
+        let input_string = "= x 5\r\nprint x";
+        let mut parser = Parser::new(input_string);
+        parser.display_errors = false;
+        let result = parser.parse();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+
+        let mut el_option = result.clone().output_arena_get_nth_last_child_element(1);
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(5));
+            }
+            _ => assert!(true, false),
+        }
+
+        el_option = result.clone().output_arena_get_nth_last_child_element(0);
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Print));
+                assert_eq!(el.int64, Some(5));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //names are read as el_var reads them, so whatever can be assigned can be printed
+        parser = Parser::new("= é_1 5\r\nprint é_1;");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.success, true);
+        parser = Parser::new("print 1x");
+        parser.display_errors = false;
+        let result = parser.clone().fn_print();
+        assert_eq!(result.input_remaining(), "print 1x");
+        assert_eq!(result.last_error(), Some("el_var - invalid name"));
+        assert_eq!(result.success, false);
+
+        //undefined variable
+        parser = Parser::new("print y");
+        parser.display_errors = false;
+        let result = parser.clone().fn_print();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(
+            result
+                .clone()
+                .output_arena_get_last_child_element()
+                .is_none(),
+            true
+        );
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_variable_assign() {
+        //not a el_var assignment
+        let mut input_string = " = x 1";
+        let mut result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), " = x 1");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //"= x (+ 1 (+ 2 (+ 3 4)))", i.e. x = 1 + (2 + (3 + 4))
+        //as below with brackets notation
+        input_string = "= x (+ 1 (+ 2 (+ 3 4)))";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(10));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //"= x + 1 + 2 + 3 4", i.e. x = 1 + (2 + (3 + 4))
+        //short name el_var assignment to sum of 2 short ints, where the second is 2 nested sums of 2 short ints
+        input_string = "= x + 1 + 2 + 3 4";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(10));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //"= x + + 1 2 + 3 4", i.e. x = (1 + 2) + (3 + 4))
+        //short name el_var assignment to sum of 2 short ints, where the second is 2 nested sums of 2 short ints, different format
+        input_string = "= x + + 1 2 + 3 4";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(10));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //"= x + 1 + 2 3", i.e. x = 1 + (2 + 3)
+        //short name el_var assignment to sum of 2 short ints, where the second is a sum of 2 short ints
+        input_string = "= x + 1 + 2 3";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(6));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //short name el_var assignment to sum of 2 short ints
+        input_string = "= x + 1 2";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(3));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //short name el_var assignment to sum of 2 long floats
+        input_string = "= x + 11111.11111 22222.22222";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.float64, Some(33333.33333));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //short name el_var assignment to short el_int
+        input_string = "= x 1";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(1));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //short name el_var assignment to short el_int with newlines
+        input_string = "= x 1\r\n\r\n\r\n";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(1));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //short name el_var assignment to short el_int with trailing whitespace before newline
+        input_string = "= x 1   \r\n";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.int64, Some(1));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //typed el_var assignment to matching el_int
+        input_string = "= x:int 5";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.var_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(5));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //typed el_var assignment to mismatched el_float
+        let mut parser = Parser::new("= x:int 5.0");
+        parser.display_errors = false;
+        result = parser.clone().fn_var_assign();
+        assert_eq!(result.success, false);
+
+        //each type annotation can be assigned a matching value
+        for (input, expected) in [
+            ("= x:float 1.5", ParserElValue::F64(1.5)),
+            ("= x:str \"a\"", ParserElValue::Str("a".to_string())),
+            ("= x:bool true", ParserElValue::Bool(true)),
+            ("= x:bool == 1 2", ParserElValue::Bool(false)),
+        ]
+        .iter()
+        {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            result = parser.clone().fn_var_assign().evaluate();
+            assert_eq!(result.input_remaining(), "", "{}", input);
+            assert_eq!(
+                result.output_arena_els(),
+                vec![ParserEl::new_var("x".to_string(), Some(expected.clone()))],
+                "{}",
+                input
+            );
+            assert_eq!(result.success, true, "{}", input);
+        }
+
+        //but not a value of another type
+        for input in ["= x:float 1", "= x:str 1", "= x:bool \"true\""].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            result = parser.clone().fn_var_assign();
+            assert_eq!(result.success, false, "{}", input);
+            assert_eq!(
+                result
+                    .last_error()
+                    .map(|error| error.contains("is declared as")),
+                Some(true),
+                "{}",
+                input
+            );
+        }
+
+        //long name el_var with grapheme assignment to long negative el_int
+        input_string = "= éxample_long_variable_name -123456";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("éxample_long_variable_name".to_string()));
+                assert_eq!(el.int64, Some(-123456));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //short name el_var assignment to short el_float
+        input_string = "= x 1.2";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.float64, Some(1.2));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //short name el_var assignment to long negative el_float
+        input_string = "= x -11111.22222";
+        result = Parser::new_and_parse(input_string, Parser::fn_var_assign).evaluate();
+        assert_eq!(result.input_original, input_string);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+                assert_eq!(el.float64, Some(-11111.22222));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_variable() {
+        //not a el_var
+        let mut parser = Parser::new(" x = 1");
+        parser.display_errors = false;
+        let result = parser.clone().el_var();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " x = 1");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //short name el_var
+        parser = Parser::new("x = 1");
+        parser.display_errors = false;
+        let result = parser.clone().el_var();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "= 1");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("x".to_string()));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //unknown type annotation
+        parser = Parser::new("x:nope = 1");
+        parser.display_errors = false;
+        let result = parser.clone().el_var();
+        assert_eq!(result.success, false);
+
+        //names start with a letter or an underscore
+        for input in ["x1 = 1", "_x = 1", "é = 1", "e\u{301} = 1"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().el_var();
+            assert_eq!(result.input_remaining(), "= 1", "{}", input);
+            assert_eq!(result.success, true, "{}", input);
+        }
+        for input in ["1x = 1", "1 = 1", "-x = 1", ":int = 1"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().el_var();
+            assert_eq!(result.output_arena_els(), vec![], "{}", input);
+            assert_eq!(
+                result.last_error(),
+                Some("el_var - invalid name"),
+                "{}",
+                input
+            );
+            assert_eq!(result.success, false, "{}", input);
+        }
+
+        //the name ends at any whitespace, so it doesn't run into the next line
+        parser = Parser::new("= x\r\n= y 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign();
+        assert_eq!(result.input_remaining(), "= x\r\n= y 2");
+        assert_eq!(result.last_error(), Some("el_var"));
+        assert_eq!(result.last_error_position(), Some(3));
+        assert_eq!(result.success, false);
+
+        //long name el_var with grapheme
+        parser = Parser::new("éxample_long_variable_name = 123.45");
+        parser.display_errors = false;
+        let result = parser.clone().el_var();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "= 123.45");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Var));
+                assert_eq!(el.var_name, Some("éxample_long_variable_name".to_string()));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_float() {
+        //not a el_float
+        let mut parser = Parser::new("a123.456");
+        parser.display_errors = false;
+        let result = parser.clone().el_float();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "a123.456");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //positive small el_float
+        parser = Parser::new("12.34");
+        parser.display_errors = false;
+        let result = parser.clone().el_float();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(12.34));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //positive large el_float
+        parser = Parser::new("123456.78");
+        parser.display_errors = false;
+        let result = parser.clone().el_float();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(123456.78));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //negative el_float
+        parser = Parser::new("-123456.78");
+        parser.display_errors = false;
+        let result = parser.clone().el_float();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(-123456.78));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //no digits before the point
+        parser = Parser::new(".5");
+        parser.display_errors = false;
+        let result = parser.clone().el_float();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(0.5));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //no digits after the point
+        parser = Parser::new("5.");
+        parser.display_errors = false;
+        let result = parser.clone().el_float();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(5.0));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //no digits at all
+        parser = Parser::new(".");
+        parser.display_errors = false;
+        let result = parser.clone().el_float();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), ".");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //grouped digits and exponents, together or not
+        for (input_str, expected, remaining) in [
+            ("1_000.5", 1000.5, ""),
+            ("1.5e-3", 0.0015, ""),
+            ("1_000.5e-3", 1.0005, ""),
+            ("0.000_5", 0.0005, ""),
+            ("-2.5E+2", -250.0, ""),
+            ("1e5", 100000.0, ""),
+            (".5e1", 5.0, ""),
+            ("1.5e x", 1.5, "e x"),
+        ] {
+            parser = Parser::new(input_str);
+            parser.display_errors = false;
+            let result = parser.clone().el_float();
+            assert_eq!(result.input_remaining(), remaining, "{}", input_str);
+            assert_eq!(
+                result.output_arena_els(),
+                vec![ParserEl::new_float(expected)],
+                "{}",
+                input_str
+            );
+            assert_eq!(result.chomp, "", "{}", input_str);
+            assert_eq!(result.success, true, "{}", input_str);
+        }
+
+        //malformed, or an int
+        for input_str in [
+            "1_.5", "1._5", "_1.5", "1__0.5", "1.5_", "1.5_e3", "1", "1_000", "e5",
+        ] {
+            parser = Parser::new(input_str);
+            parser.display_errors = false;
+            let result = parser.clone().el_float();
+            assert_eq!(result.input_remaining(), input_str);
+            assert_eq!(result.output_arena_els(), vec![], "{}", input_str);
+            assert_eq!(result.chomp, "", "{}", input_str);
+            assert_eq!(result.success, false, "{}", input_str);
+        }
+
+        //explicitly positive el_float
+        parser = Parser::new("+1.5");
+        parser.display_errors = false;
+        let result = parser.clone().el_float();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64, Some(1.5));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_float_special() {
+        let tests: Vec<(&str, f64)> = vec![
+            ("inf", f64::INFINITY),
+            ("+inf", f64::INFINITY),
+            ("-inf", f64::NEG_INFINITY),
+        ];
+        for (input, expected) in tests {
+            let mut parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().el_float_special();
+            assert_eq!(result.input_remaining(), "", "{}", input);
+            let el_option = result.clone().output_arena_get_last_child_element();
+            match el_option {
+                Some(el) => {
+                    assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                    assert_eq!(el.float64.unwrap().is_infinite(), true);
+                    assert_eq!(el.float64, Some(expected));
+                }
+                _ => assert!(true, false),
+            }
+            assert_eq!(result.chomp, "", "{}", input);
+            assert_eq!(result.success, true, "{}", input);
+        }
+
+        //NaN != NaN, so check it with is_nan
+        let mut parser = Parser::new("nan x");
+        parser.display_errors = false;
+        let result = parser.clone().el_float_special();
+        assert_eq!(result.input_remaining(), " x");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Float64));
+                assert_eq!(el.float64.unwrap().is_nan(), true);
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //only whole words
+        for input in ["info", "nano", "-in", "1.5"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().el_float_special();
+            assert_eq!(result.input_remaining(), *input);
+            assert_eq!(result.chomp, "", "{}", input);
+            assert_eq!(result.success, false, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_int() {
+        //not an el_int
+        let mut parser = Parser::new("a123");
+        parser.display_errors = false;
+        let result = parser.clone().el_int();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "a123");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //positive small el_int
+        parser = Parser::new("12");
+        parser.display_errors = false;
+        let result = parser.clone().el_int();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(12));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //positive large el_int
+        parser = Parser::new("123456");
+        parser.display_errors = false;
+        let result = parser.clone().el_int();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(123456));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //negative el_int
+        parser = Parser::new("-123456");
+        parser.display_errors = false;
+        let result = parser.clone().el_int();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(-123456));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //underscores between digits
+        for (input_str, expected) in [("1_000", 1000), ("1000", 1000), ("-1_000_000", -1000000)] {
+            parser = Parser::new(input_str);
+            parser.display_errors = false;
+            let result = parser.clone().el_int();
+            assert_eq!(result.input_remaining(), "");
+            let el_option = result.output_arena_get_last_child_element();
+            match el_option {
+                Some(el) => {
+                    assert_eq!(el.int64, Some(expected));
+                }
+                _ => assert!(true, false),
+            }
+            assert_eq!(result.success, true);
+        }
+
+        //underscores not between digits
+        for input_str in ["_1", "1_", "1__0"] {
+            parser = Parser::new(input_str);
+            parser.display_errors = false;
+            let result = parser.clone().el_int();
+            assert_eq!(result.output_arena_get_last_child_element().is_none(), true);
+            assert_eq!(result.success, false);
+        }
+
+        //hexadecimal, binary and octal, with an optional sign
+        for (input_str, expected) in [
+            ("0xFF", 255),
+            ("0xff", 255),
+            ("0b1010", 10),
+            ("0o17", 15),
+            ("-0x10", -16),
+            ("+0b1", 1),
+            ("0x7FFF_FFFF_FFFF_FFFF", i64::MAX),
+            ("-0x8000000000000000", i64::MIN),
+            ("42", 42),
+        ] {
+            parser = Parser::new(input_str);
+            parser.display_errors = false;
+            let result = parser.clone().el_int();
+            assert_eq!(result.input_remaining(), "", "{}", input_str);
+            let el_option = result.output_arena_get_last_child_element();
+            match el_option {
+                Some(el) => {
+                    assert_eq!(el.int64, Some(expected), "{}", input_str);
+                }
+                _ => assert!(true, false),
+            }
+            assert_eq!(result.success, true, "{}", input_str);
+        }
+
+        //a digit which isn't in the base stops the number
+        parser = Parser::new("0b102");
+        parser.display_errors = false;
+        let result = parser.clone().el_int();
+        assert_eq!(result.input_remaining(), "2");
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(2)]);
+
+        //a prefix without any digits
+        for input_str in ["0x", "0xG", "0xZ", "0b_1", "-0o", "1_"] {
+            parser = Parser::new(input_str);
+            parser.display_errors = false;
+            let result = parser.clone().el_int();
+            assert_eq!(result.input_remaining(), input_str);
+            assert_eq!(result.chomp, "");
+            assert_eq!(result.last_error(), Some("el_int"));
+            assert_eq!(result.output_arena_get_last_child_element().is_none(), true);
+            assert_eq!(result.success, false, "{}", input_str);
+        }
+
+        //too large fails cleanly, rather than panicking
+        for input_str in [
+            "9223372036854775808",
+            "0x8000000000000000",
+            "-0b1_0000000000000000000000000000000000000000000000000000000000000001",
+        ] {
+            parser = Parser::new(input_str);
+            parser.display_errors = false;
+            let result = parser.clone().el_int();
+            assert_eq!(result.input_remaining(), input_str);
+            assert_eq!(result.chomp, "");
+            assert_eq!(result.last_error(), Some("el_int - too large"));
+            assert_eq!(result.success, false, "{}", input_str);
+        }
+
+        //explicitly positive el_int
+        parser = Parser::new("+5");
+        parser.display_errors = false;
+        let result = parser.clone().el_int();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(5));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_prim_word_cap_insensitive() {
+        for input in ["Print x", "print x"].iter() {
+            let parser = Parser::new(input);
+            let result = parser.clone().prim_word_cap_insensitive("print");
+            assert_eq!(result.input_original, parser.input_original);
+            assert_eq!(result.input_remaining(), " x");
+            assert_eq!(result.chomp, input[..5]);
+            assert_eq!(result.success, true);
+        }
+
+        //only the first character
+        for input in ["PRINT x", "pRint x", "prin"].iter() {
+            let mut parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().prim_word_cap_insensitive("print");
+            assert_eq!(result.input_remaining(), *input);
+            assert_eq!(result.chomp, "", "{}", input);
+            assert_eq!(result.success, false, "{}", input);
+        }
+
+        //a multi-byte first character
+        let parser = Parser::new("Éte");
+        let result = parser.clone().prim_word_cap_insensitive("éte");
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "Éte");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_prim_word_fold() {
+        let mut parser = Parser::new("café x");
+        parser.display_errors = false;
+        let result = parser.clone().prim_word_fold("cafe");
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " x");
+        assert_eq!(result.chomp, "café");
+        assert_eq!(result.success, true);
+
+        //a combining accent, rather than a precomposed é
+        parser = Parser::new("cafe\u{301} x");
+        parser.display_errors = false;
+        let result = parser.clone().prim_word_fold("café");
+        assert_eq!(result.input_remaining(), " x");
+        assert_eq!(result.chomp, "cafe\u{301}");
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("cafes");
+        parser.display_errors = false;
+        let result = parser.clone().prim_word_fold("cafe x");
+        assert_eq!(result.input_remaining(), "cafes");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_prim_keyword() {
+        let mut parser = Parser::new("in x");
+        parser.display_errors = false;
+        let result = parser.clone().prim_keyword("in");
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " x");
+        assert_eq!(result.chomp, "in");
+        assert_eq!(result.success, true);
+
+        //followed by punctuation, or the end of the input
+        for input in ["in(x)", "in", "in\r\n"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().prim_keyword("in");
+            assert_eq!(result.chomp, "in", "{}", input);
+            assert_eq!(result.success, true, "{}", input);
+        }
+
+        //only the start of a longer word
+        for input in ["int", "in_x", "in2"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().prim_keyword("in");
+            assert_eq!(result.input_remaining(), *input);
+            assert_eq!(result.chomp, "", "{}", input);
+            assert_eq!(result.success, false, "{}", input);
+        }
+
+        //prim_word does match the start of a longer word
+        parser = Parser::new("int");
+        parser.display_errors = false;
+        let result = parser.clone().prim_word("in");
+        assert_eq!(result.input_remaining(), "t");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_prim_sign() {
+        let mut parser = Parser::new("+5");
+        parser.display_errors = false;
+        let result = parser.clone().prim_sign();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "5");
+        assert_eq!(result.chomp, "+");
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("-5");
+        parser.display_errors = false;
+        let result = parser.clone().prim_sign();
+        assert_eq!(result.input_remaining(), "5");
+        assert_eq!(result.chomp, "-");
+        assert_eq!(result.success, true);
+
+        //no sign still succeeds
+        parser = Parser::new("5");
+        parser.display_errors = false;
+        let result = parser.clone().prim_sign();
+        assert_eq!(result.input_remaining(), "5");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_uint() {
+        let mut parser = Parser::new("5");
+        parser.display_errors = false;
+        let result = parser.clone().el_uint();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(5));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //no sign allowed
+        parser = Parser::new("-5");
+        parser.display_errors = false;
+        let result = parser.clone().el_uint();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "-5");
+        assert_eq!(
+            result
+                .clone()
+                .output_arena_get_last_child_element()
+                .is_none(),
+            true
+        );
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //too large for an Int64
+        parser = Parser::new("99999999999999999999");
+        parser.display_errors = false;
+        let result = parser.clone().el_uint();
+        assert_eq!(result.input_remaining(), "99999999999999999999");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_el_null() {
+        let mut parser = Parser::new("null");
+        parser.display_errors = false;
+        let result = parser.clone().el_null();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "");
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Null));
+                assert_eq!(el.int64, None);
+                assert_eq!(el.float64, None);
+                assert_eq!(el.string, None);
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        for input in ["nul", "nullable"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().el_null();
+            assert_eq!(result.input_remaining(), *input);
+            assert_eq!(result.chomp, "");
+            assert_eq!(result.output_arena_get_last_child_element().is_none(), true);
+            assert_eq!(result.last_error(), Some("el_null"));
+            assert_eq!(result.success, false);
+        }
+    }
+
+    #[test]
+    fn test_el_bool() {
+        let mut parser = Parser::new("false rest");
+        parser.display_errors = false;
+        let result = parser.clone().el_bool();
+        assert_eq!(result.input_remaining(), " rest");
+        assert_eq!(result.chomp, "");
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Bool));
+                assert_eq!(el.boolean, Some(false));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //only the whole word
+        parser = Parser::new("trueish");
+        parser.display_errors = false;
+        let result = parser.clone().el_bool();
+        assert_eq!(result.input_remaining(), "trueish");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.last_error(), Some("el_bool"));
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_el_char() {
+        let mut parser = Parser::new("'a' rest");
+        parser.display_errors = false;
+        let result = parser.clone().el_char();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " rest");
+        assert_eq!(result.chomp, "");
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Char));
+                assert_eq!(el.character, Some('a'));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("'\\n'");
+        parser.display_errors = false;
+        let result = parser.clone().el_char();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Char));
+                assert_eq!(el.character, Some('\n'));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //empty
+        parser = Parser::new("''");
+        parser.display_errors = false;
+        let result = parser.clone().el_char();
+        assert_eq!(result.input_remaining(), "''");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.last_error(), Some("el_char - empty"));
+        assert_eq!(result.success, false);
+
+        //more than one character
+        parser = Parser::new("'ab'");
+        parser.display_errors = false;
+        let result = parser.clone().el_char();
+        assert_eq!(result.input_remaining(), "'ab'");
+        assert_eq!(result.success, false);
+
+        parser = Parser::new("'e\u{301}'");
+        parser.display_errors = false;
+        let result = parser.clone().el_char();
+        assert_eq!(
+            result.last_error(),
+            Some("el_char - more than one character")
+        );
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_el_duration() {
+        let mut parser = Parser::new("3h30m rest");
+        parser.display_errors = false;
+        let result = parser.clone().el_duration();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " rest");
+        assert_eq!(result.chomp, "");
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Duration));
+                assert_eq!(el.int64, Some(12600));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("45s");
+        parser.display_errors = false;
+        let result = parser.clone().el_duration();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Duration));
+                assert_eq!(el.int64, Some(45));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.success, true);
+
+        //no unit
+        parser = Parser::new("3h30");
+        parser.display_errors = false;
+        let result = parser.clone().el_duration();
+        assert_eq!(result.input_remaining(), "3h30");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.last_error(), Some("el_duration - missing unit"));
+        assert_eq!(result.success, false);
+
+        //empty
+        parser = Parser::new("");
+        parser.display_errors = false;
+        let result = parser.clone().el_duration();
+        assert_eq!(result.last_error(), Some("el_duration"));
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_el_list() {
+        let mut parser = Parser::new("[1, 2, 3] rest");
+        parser.display_errors = false;
+        let result = parser.clone().el_list();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " rest");
+        assert_eq!(result.chomp, "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::List));
+            }
+            _ => assert!(true, false),
+        }
+        let arena = &result.output_arena;
+        let list_id = result.output_arena_get_nth_last_child_id(0).unwrap();
+        let items: Vec<ParserEl> = list_id
+            .children(arena)
+            .map(|child_id| ParserEl::from(arena.get(child_id).unwrap().get().clone()))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ParserEl::new_int(1),
+                ParserEl::new_int(2),
+                ParserEl::new_int(3)
+            ]
+        );
+        assert_eq!(result.success, true);
+
+        //empty
+        parser = Parser::new("[]");
+        parser.display_errors = false;
+        let result = parser.clone().el_list();
+        assert_eq!(result.input_remaining(), "");
+        let arena = &result.output_arena;
+        let list_id = result.output_arena_get_nth_last_child_id(0).unwrap();
+        assert_eq!(
+            arena.get(list_id).unwrap().get().el_type,
+            Some(ParserElementType::List)
+        );
+        assert_eq!(list_id.children(arena).count(), 0);
+        assert_eq!(result.success, true);
+
+        //mixed
+        parser = Parser::new("[1, \"two\"]");
+        parser.display_errors = false;
+        let result = parser.clone().el_list();
+        assert_eq!(result.input_remaining(), "");
+        let arena = &result.output_arena;
+        let list_id = result.output_arena_get_nth_last_child_id(0).unwrap();
+        let items: Vec<ParserEl> = list_id
+            .children(arena)
+            .map(|child_id| ParserEl::from(arena.get(child_id).unwrap().get().clone()))
+            .collect();
+        assert_eq!(
+            items,
+            vec![ParserEl::new_int(1), ParserEl::new_str("two".to_string())]
+        );
+        assert_eq!(result.success, true);
+
+        //unclosed, leaves nothing behind
+        parser = Parser::new("[1, 2");
+        parser.display_errors = false;
+        let result = parser.clone().el_list();
+        assert_eq!(result.input_remaining(), "[1, 2");
+        assert_eq!(result.output_arena_get_nth_last_child_id(0), None);
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_last_error() {
+        //a failed el_int keeps its error, even though the fail is passed through later functions
+        let mut parser = Parser::new("a123");
+        parser.display_errors = false;
+        let result = parser.clone().el_int().prim_char().prim_digit();
+        assert_eq!(result.success, false);
+        assert_eq!(result.last_error().unwrap().contains("el_int"), true);
+        assert_eq!(result.last_error_position(), Some(0));
+
+        //a successful parse has no error, even though some attempts inside it failed
+        parser = Parser::new("= x 1\r\n= y 2.5");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.success, true);
+        assert_eq!(result.last_error(), None);
+        assert_eq!(result.last_error_position(), None);
+
+        //a function which fails because an inner parser did passes on the inner error, rather than its own name
+        parser = Parser::new("= x 1.5.5\r\n= y 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign();
+        assert_eq!(result.input_remaining(), "= x 1.5.5\r\n= y 2");
+        assert_eq!(result.last_error(), Some("prim_statement_end"));
+        assert_eq!(result.last_error_position(), Some(7));
+        assert_eq!(result.success, false);
+        let (_, errors) = parser.clone().parse_all();
+        assert_eq!(errors[0].message(), "prim_statement_end");
+
+        parser = Parser::new("= 1x 2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign();
+        assert_eq!(result.last_error(), Some("el_var - invalid name"));
+
+        //but its own name if it failed at the start, e.g. it isn't an assignment
+        parser = Parser::new("x");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_assign();
+        assert_eq!(result.last_error(), Some("fn_var_assign"));
+        assert_eq!(result.last_error_position(), Some(0));
+    }
+
+    #[test]
+    fn test_combi_first_success_of() {
+        //appends an el_int, then fails
+        fn int_then_x(p: Parser) -> Parser {
+            p.el_int().prim_word("x")
+        }
+        //the throwaway el_int from int_then_x should already be gone
+        fn clean_then_int(mut p: Parser) -> Parser {
+            if p.clone().output_arena_get_last_child_element().is_some() {
+                p.success = false;
+            }
+            p.el_int()
+        }
+        let mut parser = Parser::new("12y");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_first_success_of(&[int_then_x, clean_then_int].to_vec());
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "y");
+        assert_eq!(
+            result
+                .output_arena
+                .iter()
+                .filter(|n| !n.is_removed())
+                .count(),
+            2
+        );
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(12));
+            }
+            _ => assert!(true, false),
+        }
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //all fail, so nothing is consumed or appended
+        let result = parser
+            .clone()
+            .combi_first_success_of(&[int_then_x, Parser::prim_eof].to_vec());
+        assert_eq!(result.input_remaining(), "12y");
+        assert_eq!(
+            result
+                .clone()
+                .output_arena_get_last_child_element()
+                .is_none(),
+            true
+        );
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //a failed attempt which removed or changed an element which was already there is undone too
+        let mut parser = Parser::new("x")
+            .output_arena_append_element(ParserElement::new_int(1))
+            .output_arena_append_element(ParserElement::new_int(2));
+        parser.display_errors = false;
+        let result = parser.clone().combi_first_success_of(
+            &[
+                |s: Parser| s.output_arena_remove_child_at(0).prim_word("nope"),
+                |s: Parser| {
+                    s.map_last_element(|_| ParserElement::new_int(3))
+                        .prim_word("nope")
+                },
+                |s: Parser| s.prim_word("x"),
+            ]
+            .to_vec(),
+        );
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(
+            result.output_arena_els(),
+            vec![ParserEl::new_int(1), ParserEl::new_int(2)]
+        );
+        assert_eq!(result.success, true);
+
+        //even if it was removed by an inner attempt which succeeded
+        let result = parser.clone().combi_first_success_of(
+            &[
+                |s: Parser| {
+                    s.combi_optional(|s: Parser| s.output_arena_remove_child_at(0))
+                        .combi_first_success_of(
+                            &[|s: Parser| s.output_arena_remove_child_at(0)].to_vec(),
+                        )
+                        .prim_word("nope")
+                },
+                |s: Parser| s.prim_word("x"),
+            ]
+            .to_vec(),
+        );
+        assert_eq!(
+            result.output_arena_els(),
+            vec![ParserEl::new_int(1), ParserEl::new_int(2)]
+        );
+        assert_eq!(result.success, true);
+
+        //and the indents and depth are put back as they were
+        let result = parser.clone().combi_first_success_of(
+            &[
+                |mut s: Parser| {
+                    s.depth += 1;
+                    s.indent_push(4).prim_word("nope")
+                },
+                |s: Parser| s.prim_word("x"),
+            ]
+            .to_vec(),
+        );
+        assert_eq!(result.indents, Vec::<usize>::new());
+        assert_eq!(result.depth, 0);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_expect() {
+        let mut parser = Parser::new("= x y");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .prim_word("= ")
+            .chomp_clear()
+            .el_var()
+            .expect(Parser::el_int, "expected an integer");
+        assert_eq!(result.last_error(), Some("expected an integer"));
+        assert_eq!(result.last_error_position(), Some(4));
+        assert_eq!(result.success, false);
+
+        //no error if it succeeds
+        parser = Parser::new("5");
+        parser.display_errors = false;
+        let result = parser.clone().expect(Parser::el_int, "expected an integer");
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(5)]);
+        assert_eq!(result.last_error(), None);
+        assert_eq!(result.success, true);
+
+        //an earlier fail is passed on, rather than replaced
+        parser = Parser::new("x");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .el_int()
+            .expect(Parser::el_bool, "expected a bool");
+        assert_eq!(result.last_error(), Some("el_int"));
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_combi_fold() {
+        let sum = |acc: ParserElement, el: ParserElement| {
+            ParserElement::checked_add(&acc, &el).unwrap_or(acc)
+        };
+        let mut parser = Parser::new("1 2 3 4");
+        parser.display_errors = false;
+        let result = parser.clone().combi_fold(
+            |s: Parser| s.combi_token(Parser::el_int),
+            ParserElement::new_int(0),
+            sum,
+        );
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(10)]);
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
 
-        input_str = "a";
-        language_string = "@.";
-        result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "a");
+        //only the elements from the items are folded, and it stops at the first which isn't one
+        parser = Parser::new("1 2 x");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .output_arena_append_element(ParserElement::new_str("total".to_string()))
+            .combi_fold(
+                |s: Parser| s.combi_token(Parser::el_int),
+                ParserElement::new_int(0),
+                sum,
+            );
+        assert_eq!(result.input_remaining(), "x");
+        assert_eq!(
+            result.output_arena_els(),
+            vec![ParserEl::new_str("total".to_string()), ParserEl::new_int(3)]
+        );
         assert_eq!(result.success, true);
-    }
 
-    #[test]
-    fn test_lang_prim_eof() {
-        let input_str = "a";
-        let language_string = "@.";
-        let result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "a");
+        //no items is just the initial element
+        parser = Parser::new("x");
+        parser.display_errors = false;
+        let result = parser.clone().combi_fold(
+            |s: Parser| s.combi_token(Parser::el_int),
+            ParserElement::new_int(0),
+            sum,
+        );
+        assert_eq!(result.input_remaining(), "x");
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(0)]);
         assert_eq!(result.success, true);
     }
 
     #[test]
-    fn test_lang_prim_eols() {
-        let input_str = "\r\n\r\n\r\n!\n";
-        let language_string = ",@,";
-        let result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\r\n\r\n\r\n!\n");
+    fn test_combi_longest_of() {
+        let mut parser = Parser::new("int x");
+        parser.display_errors = false;
+        let in_or_int = [
+            |s: Parser| s.prim_word("in"),
+            |s: Parser| s.prim_word("int"),
+        ];
+        let result = parser.clone().combi_longest_of(&in_or_int);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " x");
+        assert_eq!(result.chomp, "int");
         assert_eq!(result.success, true);
-    }
 
-    #[test]
-    fn test_lang_prim_digit() {
-        let input_str = "0123456789";
-        let language_string = "##########";
-        let result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "0123456789");
+        //whereas the first match wins with combi_first_success_of
+        let result = parser.clone().combi_first_success_of(&in_or_int.to_vec());
+        assert_eq!(result.input_remaining(), "t x");
+        assert_eq!(result.chomp, "in");
+
+        //only the longest one's elements are kept, e.g. a float rather than the int before its "."
+        parser = Parser::new("1.5");
+        parser.display_errors = false;
+        let result =
+            parser
+                .clone()
+                .combi_longest_of(&[Parser::el_int, Parser::el_float, Parser::el_bool]);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_float(1.5)]);
         assert_eq!(result.success, true);
-    }
 
-    #[test]
-    fn test_lang_prim_char() {
-        let input_str = "+%!";
-        let language_string = "@@@";
-        let result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "+%!");
+        //ties go to the first, i.e. whether the element is appended or just the word matched
+        parser = Parser::new("12");
+        parser.display_errors = false;
+        let int_or_word: [ParserFunction; 2] = [Parser::el_int, |s: Parser| s.prim_word("12")];
+        let result = parser.clone().combi_longest_of(&int_or_word);
+        assert_eq!(result.output_arena_els(), vec![ParserEl::new_int(12)]);
+        assert_eq!(result.success, true);
+        let word_or_int: [ParserFunction; 2] = [|s: Parser| s.prim_word("12"), Parser::el_int];
+        let result = parser.clone().combi_longest_of(&word_or_int);
+        assert_eq!(result.output_arena_els(), vec![]);
+        assert_eq!(result.chomp, "12");
         assert_eq!(result.success, true);
+
+        //none match
+        parser = Parser::new("x");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_longest_of(&[Parser::el_int, Parser::el_float]);
+        assert_eq!(result.input_remaining(), "x");
+        assert_eq!(result.output_arena_els(), vec![]);
+        assert_eq!(result.last_error(), Some("combi_longest_of"));
+        assert_eq!(result.success, false);
     }
 
     #[test]
-    fn test_lang_prim_quote() {
-        let input_str = "\"\"\"";
-        let language_string = "\"\"\"";
-        let result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "");
-        assert_eq!(result.success, true);
+    fn test_combi_first_success_of_furthest_error() {
+        //el_bool fails straight away, the other gets as far as the value before its el_int fails
+        let mut parser = Parser::new("= x y");
+        parser.display_errors = false;
+        let result = parser.clone().combi_first_success_of(
+            &[Parser::el_bool, |s: Parser| {
+                s.prim_word("= ").chomp_clear().el_var().el_int()
+            }]
+            .to_vec(),
+        );
+        assert_eq!(result.input_remaining(), "= x y");
+        assert_eq!(result.last_error(), Some("el_int"));
+        assert_eq!(result.last_error_position(), Some(4));
+        assert_eq!(result.success, false);
+
+        //the same, whichever order they are in
+        let result = parser.clone().combi_first_success_of(
+            &[
+                |s: Parser| s.prim_word("= ").chomp_clear().el_var().el_int(),
+                Parser::el_bool,
+            ]
+            .to_vec(),
+        );
+        assert_eq!(result.last_error(), Some("el_int"));
+        assert_eq!(result.last_error_position(), Some(4));
+
+        //all fail at the start, so a general fail
+        parser = Parser::new("y");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_first_success_of(&[Parser::el_bool, Parser::el_int].to_vec());
+        assert_eq!(result.last_error(), Some("combi_first_success_of"));
+        assert_eq!(result.last_error_position(), Some(0));
+        assert_eq!(result.success, false);
     }
 
     #[test]
-    fn test_lang_prim_next() {
-        let input_str = "123";
-        let language_string = ">>>";
-        let result = Parser::new_and_parse_aliases(input_str, language_string);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "123");
+    fn test_combi_separated_by_trailing() {
+        let comma = |s: Parser| Parser::prim_word(s, ",").chomp_clear();
+        for input_str in ["1,2,3,", "1,2,3"].iter() {
+            let mut parser = Parser::new(input_str);
+            parser.display_errors = false;
+            let result = parser
+                .clone()
+                .combi_separated_by_trailing(Parser::el_int, comma);
+            assert_eq!(result.input_original, parser.input_original);
+            assert_eq!(result.input_remaining(), "");
+            assert_eq!(
+                result
+                    .output_arena
+                    .iter()
+                    .filter(|n| !n.is_removed())
+                    .count(),
+                4
+            );
+            assert_eq!(result.chomp, "");
+            assert_eq!(result.success, true);
+        }
+
+        //no items, so the separator isn't consumed
+        let mut parser = Parser::new(",");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_separated_by_trailing(Parser::el_int, comma);
+        assert_eq!(result.input_remaining(), ",");
         assert_eq!(result.success, true);
     }
-    //================================================================================
-    //End Language Aliases Testing
-    //================================================================================
 
     #[test]
-    //A string
-    fn test_el_string() {
-        let input_str = "\"1234\"";
-        let result = Parser::new_and_parse(input_str, Parser::el_str);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.output_arena.count(), 2);
+    fn test_combi_token() {
+        let mut parser = Parser::new("  1\t 2");
+        parser.display_errors = false;
+        let result = parser.clone().combi_token(Parser::el_int);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "2");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //flexible spacing in fn_var_sum
+        parser = Parser::new("+   1   2");
+        parser.display_errors = false;
+        let result = parser.clone().fn_var_sum().evaluate();
+        assert_eq!(result.input_remaining(), "");
         let el_option = result.clone().output_arena_get_last_child_element();
         match el_option {
             Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Str));
-                assert_eq!(el.string, Some("1234".to_string()));
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(3));
             }
             _ => assert!(true, false),
         }
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
+
+        //but the values can't run together without any space between them
+        for input in ["+ 1-2", "+ 1.5.5", "(+ 1-2)", "- 5-3"].iter() {
+            parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser
+                .clone()
+                .combi_first_success_of(&[Parser::fn_var_sum, Parser::fn_var_sub].to_vec());
+            assert_eq!(result.input_remaining(), *input, "{}", input);
+            assert_eq!(result.output_arena_els(), vec![], "{}", input);
+            assert_eq!(result.success, false, "{}", input);
+        }
     }
 
     #[test]
-    //Next
-    fn test_prim_next() {
-        //Fails
-        let input_str = "";
-        let result = Parser::new_and_parse(input_str, Parser::prim_next);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "");
-        assert_eq!(result.success, false);
-
-        //character alpha
-        let input_str = "abc";
-        let result = Parser::new_and_parse(input_str, Parser::prim_next);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "bc");
-        assert_eq!(result.chomp, "a");
+    fn test_combi_complete() {
+        let mut parser = Parser::new("12abc");
+        parser.display_errors = false;
+        let result = parser.clone().el_int();
+        assert_eq!(result.input_remaining(), "abc");
         assert_eq!(result.success, true);
 
-        //character number
-        let input_str = "1bc";
-        let result = Parser::new_and_parse(input_str, Parser::prim_next);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "bc");
-        assert_eq!(result.chomp, "1");
-        assert_eq!(result.success, true);
+        let result = parser.clone().combi_complete(Parser::el_int);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "abc");
+        assert_eq!(result.success, false);
+        assert_eq!(result.last_error(), Some("combi_complete - trailing input"));
 
-        //character special
-        let input_str = "~bc";
-        let result = Parser::new_and_parse(input_str, Parser::prim_next);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "bc");
-        assert_eq!(result.chomp, "~");
+        parser = Parser::new("12");
+        parser.display_errors = false;
+        let result = parser.clone().combi_complete(Parser::el_int);
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.success, true);
+    }
 
-        //character backslash
-        let input_str = "\\bc";
-        let result = Parser::new_and_parse(input_str, Parser::prim_next);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "bc");
-        assert_eq!(result.chomp, "\\");
+    #[test]
+    fn test_combi_many_till() {
+        let mut parser = Parser::new("abc]rest");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_many_till(Parser::prim_char, |s: Parser| Parser::prim_word(s, "]"));
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "rest");
+        assert_eq!(result.chomp, "abc]");
         assert_eq!(result.success, true);
 
-        //character unicode
-        let input_str = "ébc";
-        let result = Parser::new_and_parse(input_str, Parser::prim_next);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "bc");
-        assert_eq!(result.chomp, "é");
+        //terminator straight away
+        parser = Parser::new("]rest");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_many_till(Parser::prim_char, |s: Parser| Parser::prim_word(s, "]"));
+        assert_eq!(result.input_remaining(), "rest");
+        assert_eq!(result.chomp, "]");
         assert_eq!(result.success, true);
+
+        //eof before the terminator
+        parser = Parser::new("abc");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_many_till(Parser::prim_char, |s: Parser| Parser::prim_word(s, "]"));
+        assert_eq!(result.success, false);
+        assert_eq!(
+            result.last_error(),
+            Some("combi_many_till - terminator not found")
+        );
     }
 
-    //= x + 1 2
-    //print x
-    //= y + 1.1 2.2
-    //print y
     #[test]
-    fn test_run2() {
-        let func = |p| Parser::combi_first_success_of(p, &[Parser::fn_var_assign].to_vec());
-        let input_str = "= x 123";
-        let result = Parser::new_and_parse(input_str, func);
-        assert_eq!(result.input_original, input_str);
-        assert_eq!(result.input_remaining, "");
+    fn test_combi_chain_left() {
+        let plus = |s: Parser| Parser::prim_word(s, " + ");
+        let add = |left: ParserElement, right: ParserElement| match (left.int64, right.int64) {
+            (Some(val1), Some(val2)) => Some(ParserElement::new_int(val1 + val2)),
+            _ => None,
+        };
+        let mut parser = Parser::new("1 + 2 + 3");
+        parser.display_errors = false;
+        let result = parser.clone().combi_chain_left(Parser::el_int, plus, add);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.clone().output_arena_get_last_child_element();
+        match el_option {
+            Some(el) => {
+                assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                assert_eq!(el.int64, Some(6));
+            }
+            _ => assert!(true, false),
+        }
         assert_eq!(
             result
                 .output_arena
@@ -1528,771 +8292,953 @@ mod tests {
                 .count(),
             2
         );
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        //a trailing operator without an operand isn't consumed
+        parser = Parser::new("1 + 2 + ");
+        parser.display_errors = false;
+        let result = parser.clone().combi_chain_left(Parser::el_int, plus, add);
+        assert_eq!(result.input_remaining(), " + ");
         let el_option = result.clone().output_arena_get_last_child_element();
         match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(123));
-            }
+            Some(el) => assert_eq!(el.int64, Some(3)),
             _ => assert!(true, false),
         }
-        assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
     }
 
     #[test]
-    fn test_variable_sum() {
-        //not a valid el_var sum
-        let mut parser = Parser::new(" + test 1");
+    fn test_combi_optional() {
+        let mut parser = Parser::new("a123Test");
         parser.display_errors = false;
-        let result = parser.clone().fn_var_sum();
+        let result = parser.clone().combi_optional(Parser::prim_char);
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, " + test 1");
+        assert_eq!(result.input_remaining(), "123Test");
+        assert_eq!(result.chomp, "a");
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("a123Test");
+        parser.display_errors = false;
+        let result = parser.clone().combi_zero_or_more_of(Parser::prim_digit);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "a123Test");
         assert_eq!(result.chomp, "");
-        assert_eq!(result.success, false);
+        assert_eq!(result.success, true);
+    }
 
-        //short el_int plus short el_int, with combi_optional brackets
-        parser = Parser::new("(+ 1 2)");
+    #[test]
+    fn test_combi_named() {
+        let mut parser = Parser::new("42");
         parser.display_errors = false;
-        let result = parser.clone().fn_var_sum();
+        let result = parser.clone().combi_named("age", Parser::el_int);
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.output_arena_get_last_child_element();
         match el_option {
             Some(el) => {
                 assert_eq!(el.el_type, Some(ParserElementType::Int64));
-                assert_eq!(el.int64, Some(3));
+                assert_eq!(el.var_name, Some("age".to_string()));
+                assert_eq!(el.int64, Some(42));
             }
             _ => assert!(true, false),
         }
-        assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
 
-        //short el_int plus short el_int
-        parser = Parser::new("+ 1 2");
-        parser.display_errors = false;
-        let result = parser.clone().fn_var_sum();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
+        //an earlier element isn't named if func doesn't append one
+        let result = result.combi_named("nothing", Parser::prim_eof);
+        let el_option = result.output_arena_get_last_child_element();
         match el_option {
             Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Int64));
-                assert_eq!(el.int64, Some(3));
+                assert_eq!(el.var_name, Some("age".to_string()));
             }
             _ => assert!(true, false),
         }
-        assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
+    }
 
-        //long el_int plus long el_int
-        parser = Parser::new("+ 11111 22222");
+    #[test]
+    fn test_combi_recognize() {
+        let mut parser = Parser::new("123abc");
         parser.display_errors = false;
-        let result = parser.clone().fn_var_sum();
+        let (result, recognized) = parser
+            .clone()
+            .combi_recognize(|s: Parser| s.combi_one_or_more_of(Parser::prim_digit));
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
+        assert_eq!(result.input_remaining(), "abc");
+        assert_eq!(result.chomp, "123");
+        assert_eq!(recognized, "123");
+        assert_eq!(result.success, true);
+
+        //only the newly chomped text
+        let (result, recognized) = result.combi_recognize(Parser::prim_char);
+        assert_eq!(result.chomp, "123a");
+        assert_eq!(recognized, "a");
+        assert_eq!(result.success, true);
+
+        let (result, recognized) = result.combi_recognize(Parser::prim_digit);
+        assert_eq!(recognized, "");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_combi_or_default() {
+        let mut parser = Parser::new("x3");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .prim_word("x")
+            .chomp_clear()
+            .combi_or_default(Parser::el_int, ParserElement::new_int(1));
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
         let el_option = result.clone().output_arena_get_last_child_element();
         match el_option {
             Some(el) => {
                 assert_eq!(el.el_type, Some(ParserElementType::Int64));
-                assert_eq!(el.int64, Some(33333));
+                assert_eq!(el.int64, Some(3));
             }
             _ => assert!(true, false),
         }
-        assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
 
-        //long el_int plus negative long el_int
-        parser = Parser::new("+ 11111 -22222");
+        //no count, so it defaults to 1
+        parser = Parser::new("x-y");
         parser.display_errors = false;
-        let result = parser.clone().fn_var_sum();
+        let result = parser
+            .clone()
+            .prim_word("x")
+            .chomp_clear()
+            .combi_or_default(Parser::el_int, ParserElement::new_int(1));
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
+        assert_eq!(result.input_remaining(), "-y");
+        assert_eq!(result.chomp, "");
         let el_option = result.clone().output_arena_get_last_child_element();
         match el_option {
             Some(el) => {
                 assert_eq!(el.el_type, Some(ParserElementType::Int64));
-                assert_eq!(el.int64, Some(-11111));
+                assert_eq!(el.int64, Some(1));
             }
             _ => assert!(true, false),
         }
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_combi_optional_flagged() {
+        let mut parser = Parser::new("-5");
+        parser.display_errors = false;
+        let (result, matched) = parser
+            .clone()
+            .combi_optional_flagged(|s: Parser| Parser::prim_word(s, "-"));
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "5");
+        assert_eq!(result.chomp, "-");
+        assert_eq!(result.success, true);
+        assert_eq!(matched, true);
+
+        parser = Parser::new("5");
+        parser.display_errors = false;
+        let (result, matched) = parser
+            .clone()
+            .combi_optional_flagged(|s: Parser| Parser::prim_word(s, "-"));
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "5");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
+        assert_eq!(matched, false);
+    }
 
-        //short el_float plus short el_float
-        parser = Parser::new("+ 1.1 2.2");
+    #[test]
+    fn test_combi_zero_or_more_of() {
+        let mut parser = Parser::new("a123Test");
         parser.display_errors = false;
-        let result = parser.clone().fn_var_sum();
+        let result = parser.clone().combi_zero_or_more_of(Parser::prim_digit);
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Float64));
-                assert_eq!(el.float64, Some(3.3000000000000003)); // yikes, floats
-            }
-            _ => assert!(true, false),
-        }
+        assert_eq!(result.input_remaining(), "a123Test");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
 
-        //long el_float plus long el_float
-        parser = Parser::new("+ 11111.11111 22222.22222");
+        parser = Parser::new("123Test");
         parser.display_errors = false;
-        let result = parser.clone().fn_var_sum();
+        let result = parser.clone().combi_zero_or_more_of(Parser::prim_digit);
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Float64));
-                assert_eq!(el.float64, Some(33333.33333)); // yikes, floats
-            }
-            _ => assert!(true, false),
-        }
+        assert_eq!(result.input_remaining(), "Test");
+        assert_eq!(result.chomp, "123");
+        assert_eq!(result.success, true);
+
+        //a parser which succeeds without consuming anything doesn't loop forever
+        parser = Parser::new("abc");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_zero_or_more_of(|s: Parser| Parser::combi_optional(s, Parser::prim_digit));
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "abc");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
+    }
 
-        //long el_float plus negative long el_float
-        parser = Parser::new("+ 11111.11111 -22222.22222");
+    #[test]
+    fn test_combi_count() {
+        let mut parser = Parser::new("      x");
         parser.display_errors = false;
-        let result = parser.clone().fn_var_sum();
+        let (result, count) = parser
+            .clone()
+            .combi_count(|s: Parser| Parser::prim_word(s, "  "));
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Float64));
-                assert_eq!(el.float64, Some(-11111.11111)); // yikes, floats
-            }
-            _ => assert!(true, false),
-        }
+        assert_eq!(result.input_remaining(), "x");
+        assert_eq!(result.chomp, "      ");
+        assert_eq!(result.success, true);
+        assert_eq!(count, 3);
+
+        parser = Parser::new("x");
+        parser.display_errors = false;
+        let (result, count) = parser
+            .clone()
+            .combi_count(|s: Parser| Parser::prim_word(s, "  "));
+        assert_eq!(result.input_remaining(), "x");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
+        assert_eq!(count, 0);
     }
+
     #[test]
-    fn test_multiple_variable_assign() {
-        let input_string = "= x + 1 2\r\n= y + 3 4\r\n= z + 5.0 6.0";
-        let mut parser = Parser::new(input_string);
-        //parser.display_errors = false;
-        let result = parser.parse();
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
+    fn test_combi_one_or_more_of() {
+        let mut parser = Parser::new("a123Test");
+        parser.display_errors = false;
+        let result = parser.clone().combi_one_or_more_of(Parser::prim_digit);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "a123Test");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
 
-        let mut el_option = result.clone().output_arena_get_nth_last_child_element(2);
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(3));
-            }
-            _ => assert!(true, false),
-        }
+        parser = Parser::new("123Test");
+        parser.display_errors = false;
+        let result = parser.clone().combi_one_or_more_of(Parser::prim_digit);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "Test");
+        assert_eq!(result.chomp, "123");
+        assert_eq!(result.success, true);
+    }
 
-        el_option = result.clone().output_arena_get_nth_last_child_element(1);
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("y".to_string()));
-                assert_eq!(el.int64, Some(7));
-            }
-            _ => assert!(true, false),
-        }
+    #[test]
+    fn test_combi_at_least() {
+        let mut parser = Parser::new("123Test");
+        parser.display_errors = false;
+        let result = parser.clone().combi_at_least(3, Parser::prim_digit);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "Test");
+        assert_eq!(result.chomp, "123");
+        assert_eq!(result.success, true);
 
-        el_option = result.clone().output_arena_get_nth_last_child_element(0);
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("z".to_string()));
-                assert_eq!(el.float64, Some(11.0));
-            }
-            _ => assert!(true, false),
-        }
+        //more than the minimum
+        parser = Parser::new("12345");
+        parser.display_errors = false;
+        let result = parser.clone().combi_at_least(3, Parser::prim_digit);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "12345");
+        assert_eq!(result.success, true);
+
+        //too few, restores the parser and the output_arena
+        parser = Parser::new("12Test");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_at_least(3, |s: Parser| s.el_int().prim_word(",").chomp_clear());
+        assert_eq!(result.input_remaining(), "12Test");
+        assert_eq!(result.chomp, "");
+        assert_eq!(
+            result.last_error(),
+            Some("combi_at_least - expected at least 3")
+        );
+        assert_eq!(result.success, false);
+
+        parser = Parser::new("1,2,Test");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_at_least(3, |s: Parser| s.el_int().prim_word(",").chomp_clear());
+        assert_eq!(result.input_remaining(), "1,2,Test");
         assert_eq!(result.chomp, "");
+        assert_eq!(result.output_arena_els(), vec![]);
+        assert_eq!(
+            result.last_error(),
+            Some("combi_at_least - expected at least 3")
+        );
+        assert_eq!(result.success, false);
+
+        //zero always succeeds
+        parser = Parser::new("Test");
+        parser.display_errors = false;
+        let result = parser.clone().combi_at_least(0, Parser::prim_digit);
+        assert_eq!(result.input_remaining(), "Test");
         assert_eq!(result.success, true);
+    }
 
-        //don't create new var_name if already exists, update it
-        parser = Parser::new("= x + 1 2\r\n= x + 3 4");
+    #[test]
+    fn test_multiple_parsers() {
+        let mut parser = Parser::new("1Test");
         parser.display_errors = false;
-        let result = parser.clone().parse();
+        let result = parser.clone().prim_digit().prim_word("Te");
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(7));
-            }
-            _ => assert!(true, false),
+        assert_eq!(result.input_remaining(), "st");
+        assert_eq!(result.chomp, "1Te");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_prim_statement_end() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("; = y 2", "= y 2"),
+            (";\r\n\r\n= y 2", "= y 2"),
+            ("  ; // set x\n= y 2", "= y 2"),
+            ("\r\n= y 2", "= y 2"),
+            ("  ", ""),
+            ("", ""),
+        ];
+        for (input, remaining) in tests {
+            let mut parser = Parser::new(input);
+            parser.display_errors = false;
+            let result = parser.clone().prim_statement_end();
+            assert_eq!(result.input_original, parser.input_original);
+            assert_eq!(result.input_remaining(), remaining, "{:?}", input);
+            assert_eq!(result.chomp, "", "{:?}", input);
+            assert_eq!(result.success, true, "{:?}", input);
         }
-        assert_eq!(result.chomp, "");
+
+        //not the end of a statement
+        let mut parser = Parser::new(" 1");
+        parser.display_errors = false;
+        let result = parser.clone().prim_statement_end();
+        assert_eq!(result.input_remaining(), " 1");
+        assert_eq!(result.last_error(), Some("prim_statement_end"));
+        assert_eq!(result.success, false);
+
+        //statements separated by semicolons, newlines or both
+        parser = Parser::new("= x 1; = y 2;\r\nprint x;{\r\n= z 3; }");
+        parser.display_errors = false;
+        let result = parser.clone().parse();
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.success, true);
+        assert_eq!(
+            result.debug_tree(),
+            "Root\n  Var x I64(1)\n  Var y I64(2)\n  Print I64(1)\n  Scope\n    Var z I64(3)\n"
+        );
     }
+
     #[test]
-    fn test_variable_assign() {
-        //not a el_var assignment
-        let mut input_string = " = x 1";
-        let mut result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, " = x 1");
+    fn test_prim_eof_or_eol() {
+        //not eof or eol
+        let mut parser = Parser::new("1");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols_or_eof();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "1");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, false);
 
-        //"= x (+ 1 (+ 2 (+ 3 4)))", i.e. x = 1 + (2 + (3 + 4))
-        //as below with brackets notation
-        input_string = "= x (+ 1 (+ 2 (+ 3 4)))";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(10));
-            }
-            _ => assert!(true, false),
-        }
+        //eof
+        let mut parser = Parser::new("");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols_or_eof();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
 
-        //"= x + 1 + 2 + 3 4", i.e. x = 1 + (2 + (3 + 4))
-        //short name el_var assignment to sum of 2 short ints, where the second is 2 nested sums of 2 short ints
-        input_string = "= x + 1 + 2 + 3 4";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(10));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
+        //single eol1
+        let mut parser = Parser::new("\n");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols_or_eof();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\n");
         assert_eq!(result.success, true);
 
-        //"= x + + 1 2 + 3 4", i.e. x = (1 + 2) + (3 + 4))
-        //short name el_var assignment to sum of 2 short ints, where the second is 2 nested sums of 2 short ints, different format
-        input_string = "= x + + 1 2 + 3 4";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(10));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
+        //single eol2
+        let mut parser = Parser::new("\r\n");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols_or_eof();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\r\n");
+        assert_eq!(result.success, true);
+
+        //multiple eol1
+        let mut parser = Parser::new("\n\n\n\n");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols_or_eof();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\n\n\n\n");
         assert_eq!(result.success, true);
 
-        //"= x + 1 + 2 3", i.e. x = 1 + (2 + 3)
-        //short name el_var assignment to sum of 2 short ints, where the second is a sum of 2 short ints
-        input_string = "= x + 1 + 2 3";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(6));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
+        //multiple eol2
+        let mut parser = Parser::new("\r\n\r\n\r\n\r\n");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols_or_eof();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\r\n\r\n\r\n\r\n");
         assert_eq!(result.success, true);
+    }
 
-        //short name el_var assignment to sum of 2 short ints
-        input_string = "= x + 1 2";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(3));
-            }
-            _ => assert!(true, false),
-        }
+    #[test]
+    fn test_prim_eof() {
+        //not eof
+        let mut parser = Parser::new("1");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eof();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "1");
         assert_eq!(result.chomp, "");
-        assert_eq!(result.success, true);
+        assert_eq!(result.success, false);
 
-        //short name el_var assignment to sum of 2 long floats
-        input_string = "= x + 11111.11111 22222.22222";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.float64, Some(33333.33333));
-            }
-            _ => assert!(true, false),
-        }
+        //eof
+        let mut parser = Parser::new("");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eof();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
+    }
 
-        //short name el_var assignment to short el_int
-        input_string = "= x 1";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.int64, Some(1));
-            }
-            _ => assert!(true, false),
-        }
+    #[test]
+    fn test_prim_bom() {
+        let mut parser = Parser::new("\u{FEFF}= x 1");
+        parser.display_errors = false;
+        let result = parser.clone().prim_bom();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "= x 1");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
 
-        //short name el_var assignment to short el_int with newlines
-        input_string = "= x 1\r\n\r\n\r\n";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
+        //no bom
+        let result = result.prim_bom();
+        assert_eq!(result.input_remaining(), "= x 1");
+        assert_eq!(result.success, true);
+
+        //parse skips it
+        let result = parser.clone().parse();
+        assert_eq!(result.input_remaining(), "");
+        let el_option = result.output_arena_get_last_child_element();
         match el_option {
             Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
                 assert_eq!(el.var_name, Some("x".to_string()));
                 assert_eq!(el.int64, Some(1));
             }
             _ => assert!(true, false),
         }
-        assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
+    }
 
-        //long name el_var with grapheme assignment to long negative el_int
-        input_string = "= éxample_long_variable_name -123456";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("éxample_long_variable_name".to_string()));
-                assert_eq!(el.int64, Some(-123456));
-            }
-            _ => assert!(true, false),
-        }
+    #[test]
+    fn test_prim_eof_ws() {
+        let mut parser = Parser::new("\r\n  \n");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eof_ws();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
 
-        //short name el_var assignment to short el_float
-        input_string = "= x 1.2";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.float64, Some(1.2));
-            }
-            _ => assert!(true, false),
-        }
+        parser = Parser::new("x");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eof_ws();
+        assert_eq!(result.input_remaining(), "x");
         assert_eq!(result.chomp, "");
-        assert_eq!(result.success, true);
+        assert_eq!(result.success, false);
 
-        //short name el_var assignment to long negative el_float
-        input_string = "= x -11111.22222";
-        result = Parser::new_and_parse(input_string, Parser::fn_var_assign);
-        assert_eq!(result.input_original, input_string);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-                assert_eq!(el.float64, Some(-11111.22222));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
-        assert_eq!(result.success, true);
+        //whitespace before something else isn't eof
+        parser = Parser::new("\r\nx");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eof_ws();
+        assert_eq!(result.input_remaining(), "\r\nx");
+        assert_eq!(result.success, false);
     }
 
     #[test]
-    fn test_variable() {
-        //not a el_var
-        let mut parser = Parser::new(" x = 1");
+    fn test_prim_eols() {
+        //not an eol
+        let mut parser = Parser::new("1");
         parser.display_errors = false;
-        let result = parser.clone().el_var();
+        let result = parser.clone().prim_eols();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, " x = 1");
+        assert_eq!(result.input_remaining(), "1");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, false);
 
-        //short name el_var
-        parser = Parser::new("x = 1");
+        //single eol1
+        let mut parser = Parser::new("\n");
         parser.display_errors = false;
-        let result = parser.clone().el_var();
+        let result = parser.clone().prim_eols();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "= 1");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("x".to_string()));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\n");
         assert_eq!(result.success, true);
 
-        //long name el_var with grapheme
-        parser = Parser::new("éxample_long_variable_name = 123.45");
+        //single eol2
+        let mut parser = Parser::new("\r\n");
         parser.display_errors = false;
-        let result = parser.clone().el_var();
+        let result = parser.clone().prim_eols();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "= 123.45");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Var));
-                assert_eq!(el.var_name, Some("éxample_long_variable_name".to_string()));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\r\n");
+        assert_eq!(result.success, true);
+
+        //multiple eol1
+        let mut parser = Parser::new("\n\n\n\n");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\n\n\n\n");
+        assert_eq!(result.success, true);
+
+        //multiple eol2
+        let mut parser = Parser::new("\r\n\r\n\r\n\r\n");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\r\n\r\n\r\n\r\n");
+        assert_eq!(result.success, true);
+
+        //mixed eols
+        let mut parser = Parser::new("\r\n\n\r\r\n");
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "\r\n\n\r\r\n");
         assert_eq!(result.success, true);
     }
+
     #[test]
-    fn test_float() {
-        //not a el_float
-        let mut parser = Parser::new("a123.456");
+    fn test_prim_eol() {
+        //not an eol
+        let mut parser = Parser::new("1");
         parser.display_errors = false;
-        let result = parser.clone().el_float();
+        let result = parser.clone().prim_eol();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "a123.456");
+        assert_eq!(result.input_remaining(), "1");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, false);
 
-        //positive small el_float
-        parser = Parser::new("12.34");
+        //only the first eol1
+        let mut parser = Parser::new("\n\nrest");
         parser.display_errors = false;
-        let result = parser.clone().el_float();
+        let result = parser.clone().prim_eol();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Float64));
-                assert_eq!(el.float64, Some(12.34));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
+        assert_eq!(result.input_remaining(), "\nrest");
+        assert_eq!(result.chomp, "\n");
         assert_eq!(result.success, true);
 
-        //positive large el_float
-        parser = Parser::new("123456.78");
+        //the full eol2
+        let mut parser = Parser::new("\r\nrest");
         parser.display_errors = false;
-        let result = parser.clone().el_float();
+        let result = parser.clone().prim_eol();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Float64));
-                assert_eq!(el.float64, Some(123456.78));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
+        assert_eq!(result.input_remaining(), "rest");
+        assert_eq!(result.chomp, "\r\n");
         assert_eq!(result.success, true);
 
-        //negative el_float
-        parser = Parser::new("-123456.78");
+        //a bare eol3
+        let mut parser = Parser::new("\rrest");
         parser.display_errors = false;
-        let result = parser.clone().el_float();
+        let result = parser.clone().prim_eol();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Float64));
-                assert_eq!(el.float64, Some(-123456.78));
-            }
-            _ => assert!(true, false),
-        }
-        assert_eq!(result.chomp, "");
+        assert_eq!(result.input_remaining(), "rest");
+        assert_eq!(result.chomp, "\r");
         assert_eq!(result.success, true);
     }
 
     #[test]
-    fn test_int() {
-        //not an el_int
-        let mut parser = Parser::new("a123");
+    fn test_prim_any_newline_or_eof() {
+        //not a newline or eof
+        let mut parser = Parser::new("1");
         parser.display_errors = false;
-        let result = parser.clone().el_int();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "a123");
+        let result = parser.clone().prim_any_newline_or_eof();
+        assert_eq!(result.input_remaining(), "1");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, false);
 
-        //positive small el_int
-        parser = Parser::new("12");
-        parser.display_errors = false;
-        let result = parser.clone().el_int();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Int64));
-                assert_eq!(el.int64, Some(12));
-            }
-            _ => assert!(true, false),
-        }
+        //eof
+        let mut parser = Parser::new("");
+        parser.display_errors = false;
+        let result = parser.clone().prim_any_newline_or_eof();
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
 
-        //positive large el_int
-        parser = Parser::new("123456");
-        parser.display_errors = false;
-        let result = parser.clone().el_int();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Int64));
-                assert_eq!(el.int64, Some(123456));
-            }
-            _ => assert!(true, false),
+        for newline in ["\r\n", "\n", "\r"].iter() {
+            let input_str = format!("{}rest", newline);
+            let mut parser = Parser::new(input_str.as_str());
+            parser.display_errors = false;
+            let result = parser.clone().prim_any_newline_or_eof();
+            assert_eq!(result.input_remaining(), "rest");
+            assert_eq!(result.chomp, *newline);
+            assert_eq!(result.success, true);
         }
-        assert_eq!(result.chomp, "");
+
+        //split items on bare \r
+        let mut parser = Parser::new("1\r2");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_separated_by(Parser::el_int, |s: Parser| {
+                Parser::prim_any_newline_or_eof(s).chomp_clear()
+            });
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(
+            result.output_arena_els(),
+            vec![ParserEl::new_int(1), ParserEl::new_int(2)]
+        );
         assert_eq!(result.success, true);
 
-        //negative el_int
-        parser = Parser::new("-123456");
+        //an item and a separator which can both match nothing, e.g. at the end of the input, don't loop forever
+        let mut parser = Parser::new("");
         parser.display_errors = false;
-        let result = parser.clone().el_int();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        let el_option = result.clone().output_arena_get_last_child_element();
-        match el_option {
-            Some(el) => {
-                assert_eq!(el.el_type, Some(ParserElementType::Int64));
-                assert_eq!(el.int64, Some(-123456));
+        let result = parser.clone().combi_separated_by(
+            |s: Parser| s.combi_optional(Parser::prim_digit),
+            Parser::prim_any_newline_or_eof,
+        );
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.success, true);
+
+        let mut parser = Parser::new("1\r2\n3\r\n4");
+        parser.display_errors = false;
+        let result = parser
+            .clone()
+            .combi_separated_by(Parser::el_int, |s: Parser| {
+                Parser::prim_any_newline_or_eof(s).chomp_clear()
+            });
+        assert_eq!(result.input_remaining(), "");
+        for (i, expected) in [4, 3, 2, 1].iter().enumerate() {
+            let el_option = result.clone().output_arena_get_nth_last_child_element(i);
+            match el_option {
+                Some(el) => {
+                    assert_eq!(el.el_type, Some(ParserElementType::Int64));
+                    assert_eq!(el.int64, Some(*expected));
+                }
+                _ => assert!(true, false),
             }
-            _ => assert!(true, false),
         }
-        assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
     }
 
     #[test]
-    fn test_combi_optional() {
-        let mut parser = Parser::new("a123Test");
+    fn test_prim_until_char() {
+        let mut parser = Parser::new("name|value");
         parser.display_errors = false;
-        let result = parser.clone().combi_optional(Parser::prim_char);
+        let result = parser.clone().prim_until_char('|');
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "123Test");
-        assert_eq!(result.chomp, "a");
+        assert_eq!(result.input_remaining(), "|value");
+        assert_eq!(result.chomp, "name");
         assert_eq!(result.success, true);
 
-        parser = Parser::new("a123Test");
+        //empty run
+        parser = Parser::new("|value");
         parser.display_errors = false;
-        let result = parser.clone().combi_zero_or_more_of(Parser::prim_digit);
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "a123Test");
+        let result = parser.clone().prim_until_char('|');
+        assert_eq!(result.input_remaining(), "|value");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
+
+        //no delim
+        parser = Parser::new("name");
+        parser.display_errors = false;
+        let result = parser.clone().prim_until_char('|');
+        assert_eq!(result.input_remaining(), "name");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
     }
 
     #[test]
-    fn test_combi_zero_or_more_of() {
-        let mut parser = Parser::new("a123Test");
-        parser.display_errors = false;
-        let result = parser.clone().combi_zero_or_more_of(Parser::prim_digit);
+    fn test_prim_count_spaces() {
+        let parser = Parser::new("    x");
+        let (result, count) = parser.clone().prim_count_spaces();
+        assert_eq!(count, 4);
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "a123Test");
+        assert_eq!(result.input_remaining(), "x");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
 
-        parser = Parser::new("123Test");
-        parser.display_errors = false;
-        let result = parser.clone().combi_zero_or_more_of(Parser::prim_digit);
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "Test");
-        assert_eq!(result.chomp, "123");
+        //no spaces still succeeds
+        let (result, count) = Parser::new("x").prim_count_spaces();
+        assert_eq!(count, 0);
+        assert_eq!(result.input_remaining(), "x");
+        assert_eq!(result.success, true);
+
+        //tabs aren't spaces
+        let (result, count) = Parser::new(" \tx").prim_count_spaces();
+        assert_eq!(count, 1);
+        assert_eq!(result.input_remaining(), "\tx");
+
+        //unless they have a width
+        let (result, count) = Parser::new("\t  \tx").prim_count_indent(Some(4));
+        assert_eq!(count, 10);
+        assert_eq!(result.input_remaining(), "x");
+        assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
     }
+
     #[test]
-    fn test_combi_one_or_more_of() {
-        let mut parser = Parser::new("a123Test");
+    fn test_prim_non_space_char() {
+        let mut parser = Parser::new("a b");
         parser.display_errors = false;
-        let result = parser.clone().combi_one_or_more_of(Parser::prim_digit);
+        let result = parser.clone().prim_non_space_char();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "a123Test");
-        assert_eq!(result.chomp, "");
+        assert_eq!(result.input_remaining(), " b");
+        assert_eq!(result.chomp, "a");
+        assert_eq!(result.success, true);
+
+        let result = result.prim_non_space_char();
+        assert_eq!(result.input_remaining(), " b");
+        assert_eq!(result.chomp, "a");
         assert_eq!(result.success, false);
 
-        parser = Parser::new("123Test");
+        //prim_next does match a space
+        parser = Parser::new(" b");
         parser.display_errors = false;
-        let result = parser.clone().combi_one_or_more_of(Parser::prim_digit);
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "Test");
-        assert_eq!(result.chomp, "123");
+        let result = parser.clone().prim_next();
+        assert_eq!(result.input_remaining(), "b");
+        assert_eq!(result.chomp, " ");
         assert_eq!(result.success, true);
     }
 
     #[test]
-    fn test_multiple_parsers() {
-        let mut parser = Parser::new("1Test");
+    fn test_prim_take() {
+        let mut parser = Parser::new("ébc_rest");
         parser.display_errors = false;
-        let result = parser.clone().prim_digit().prim_word("Te");
+        let result = parser.clone().prim_take(3);
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "st");
-        assert_eq!(result.chomp, "1Te");
+        assert_eq!(result.input_remaining(), "_rest");
+        assert_eq!(result.chomp, "ébc");
         assert_eq!(result.success, true);
-    }
-    #[test]
-    fn test_prim_eof_or_eol() {
-        //not eof or eol
-        let mut parser = Parser::new("1");
+
+        parser = Parser::new("ab");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols_or_eof();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "1");
+        let result = parser.clone().prim_take(3);
+        assert_eq!(result.input_remaining(), "ab");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, false);
+    }
 
-        //eof
-        let mut parser = Parser::new("");
+    #[test]
+    fn test_prim_chomp_until_len() {
+        let mut parser = Parser::new("ab€cd");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols_or_eof();
+        let result = parser.clone().prim_chomp_until_len(4);
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "");
+        assert_eq!(result.input_remaining(), "d");
+        assert_eq!(result.chomp, "ab€c");
         assert_eq!(result.success, true);
 
-        //single eol1
-        let mut parser = Parser::new("\n");
-        parser.display_errors = false;
-        let result = parser.clone().prim_eols_or_eof();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\n");
+        //counts what is already chomped
+        let result = parser.clone().prim_word("ab").prim_chomp_until_len(3);
+        assert_eq!(result.input_remaining(), "cd");
+        assert_eq!(result.chomp, "ab€");
         assert_eq!(result.success, true);
 
-        //single eol2
-        let mut parser = Parser::new("\r\n");
-        parser.display_errors = false;
-        let result = parser.clone().prim_eols_or_eof();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\r\n");
+        let result = parser.clone().prim_word("ab").prim_chomp_until_len(1);
+        assert_eq!(result.input_remaining(), "€cd");
+        assert_eq!(result.chomp, "ab");
         assert_eq!(result.success, true);
 
-        //multiple eol1
-        let mut parser = Parser::new("\n\n\n\n");
+        //runs out of input
+        let result = parser.clone().prim_word("ab").prim_chomp_until_len(6);
+        assert_eq!(result.input_remaining(), "€cd");
+        assert_eq!(result.chomp, "ab");
+        assert_eq!(result.last_error(), Some("prim_chomp_until_len"));
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_prim_line() {
+        let mut parser = Parser::new("hello\r\nworld");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols_or_eof();
+        let result = parser.clone().prim_line();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\n\n\n\n");
+        assert_eq!(result.input_remaining(), "\r\nworld");
+        assert_eq!(result.chomp, "hello");
         assert_eq!(result.success, true);
 
-        //multiple eol2
-        let mut parser = Parser::new("\r\n\r\n\r\n\r\n");
+        //then the next line
+        let result = result.prim_eol().chomp_clear().prim_line();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "world");
+        assert_eq!(result.success, true);
+
+        //an empty line
+        parser = Parser::new("\nworld");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols_or_eof();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\r\n\r\n\r\n\r\n");
+        let result = parser.clone().prim_line();
+        assert_eq!(result.input_remaining(), "\nworld");
+        assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
     }
 
     #[test]
-    fn test_prim_eof() {
-        //not eof
-        let mut parser = Parser::new("1");
+    fn test_prim_rest() {
+        let mut parser = Parser::new("hello world");
         parser.display_errors = false;
-        let result = parser.clone().prim_eof();
+        let result = parser.clone().prim_rest();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "1");
-        assert_eq!(result.chomp, "");
-        assert_eq!(result.success, false);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "hello world");
+        assert_eq!(result.success, true);
 
-        //eof
-        let mut parser = Parser::new("");
+        //the rest, after something else
+        let result = parser.clone().prim_word("hello").prim_rest();
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "hello world");
+        assert_eq!(result.success, true);
+
+        //still succeeds at the end of the input, chomping nothing
+        parser = Parser::new("");
         parser.display_errors = false;
-        let result = parser.clone().prim_eof();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
+        let result = parser.clone().prim_rest();
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, true);
     }
 
     #[test]
-    fn test_prim_eols() {
-        //not an eol
-        let mut parser = Parser::new("1");
+    fn test_prim_grapheme() {
+        //a thumbs up with a skin tone, i.e. two chars in one grapheme
+        let mut parser = Parser::new("👍🏽 ok");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols();
+        let result = parser.clone().prim_grapheme("👍🏽");
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "1");
+        assert_eq!(result.input_remaining(), " ok");
+        assert_eq!(result.chomp, "👍🏽");
+        assert_eq!(result.success, true);
+
+        //a flag, i.e. two regional indicator chars
+        parser = Parser::new("🇬🇧");
+        parser.display_errors = false;
+        let result = parser.clone().prim_grapheme("🇬🇧");
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "🇬🇧");
+        assert_eq!(result.success, true);
+
+        //not just the start of the cluster, unlike prim_word
+        parser = Parser::new("👍🏽 ok");
+        parser.display_errors = false;
+        let result = parser.clone().prim_grapheme("👍");
+        assert_eq!(result.input_remaining(), "👍🏽 ok");
         assert_eq!(result.chomp, "");
         assert_eq!(result.success, false);
+        let result = parser.clone().prim_word("👍");
+        assert_eq!(result.input_remaining(), "🏽 ok");
 
-        //single eol1
-        let mut parser = Parser::new("\n");
+        //only one grapheme
+        parser = Parser::new("ab");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\n");
+        let result = parser.clone().prim_grapheme("ab");
+        assert_eq!(result.input_remaining(), "ab");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_prim_class() {
+        let tests: Vec<(CharClass, Vec<&str>, Vec<&str>)> = vec![
+            (CharClass::Digit, vec!["0", "9"], vec!["a", " ", "٣", ""]),
+            (CharClass::Alpha, vec!["a", "Z", "é"], vec!["1", "_", " "]),
+            (CharClass::Alnum, vec!["a", "1", "é"], vec!["_", " ", "!"]),
+            (CharClass::Space, vec![" ", "\t", "\n"], vec!["a", "_"]),
+            (CharClass::Punct, vec!["!", "_", ";"], vec!["a", "1", " "]),
+            (CharClass::Upper, vec!["A", "É"], vec!["a", "1"]),
+            (CharClass::Lower, vec!["a", "é"], vec!["A", "1"]),
+        ];
+        for (class, accepted, rejected) in tests {
+            for input in accepted {
+                let parser = Parser::new(format!("{}x", input).as_str());
+                let result = parser.clone().prim_class(class);
+                assert_eq!(result.input_remaining(), "x", "{:?} {:?}", class, input);
+                assert_eq!(result.chomp, input, "{:?} {:?}", class, input);
+                assert_eq!(result.success, true, "{:?} {:?}", class, input);
+            }
+            for input in rejected {
+                let mut parser = Parser::new(input);
+                parser.display_errors = false;
+                let result = parser.clone().prim_class(class);
+                assert_eq!(result.input_remaining(), input, "{:?} {:?}", class, input);
+                assert_eq!(result.chomp, "", "{:?} {:?}", class, input);
+                assert_eq!(result.last_error(), Some("prim_class"));
+                assert_eq!(result.success, false, "{:?} {:?}", class, input);
+            }
+        }
+
+        //one character at a time, e.g. with combi_one_or_more_of
+        let parser = Parser::new("ABc");
+        let result = parser
+            .clone()
+            .combi_one_or_more_of(|s: Parser| s.prim_class(CharClass::Upper));
+        assert_eq!(result.input_remaining(), "c");
+        assert_eq!(result.chomp, "AB");
         assert_eq!(result.success, true);
+    }
 
-        //single eol2
-        let mut parser = Parser::new("\r\n");
+    #[test]
+    fn test_prim_char_if() {
+        let mut parser = Parser::new("Ab");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols();
+        let result = parser.clone().prim_char_if(|c| c.is_uppercase());
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\r\n");
+        assert_eq!(result.input_remaining(), "b");
+        assert_eq!(result.chomp, "A");
         assert_eq!(result.success, true);
 
-        //multiple eol1
-        let mut parser = Parser::new("\n\n\n\n");
+        let result = result.prim_char_if(|c| c.is_uppercase());
+        assert_eq!(result.input_remaining(), "b");
+        assert_eq!(result.chomp, "A");
+        assert_eq!(result.success, false);
+
+        //a grapheme of more than one char doesn't match, even if its first char would
+        parser = Parser::new("E\u{301}");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols();
+        let result = parser.clone().prim_char_if(|c| c.is_uppercase());
+        assert_eq!(result.input_remaining(), "E\u{301}");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_prim_repeat_char() {
+        let mut parser = Parser::new("---\r\ntitle");
+        parser.display_errors = false;
+        let result = parser.clone().prim_repeat_char('-', 3);
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\n\n\n\n");
+        assert_eq!(result.input_remaining(), "\r\ntitle");
+        assert_eq!(result.chomp, "---");
+        assert_eq!(result.success, true);
+        let result = parser.clone().prim_repeat_char_exactly('-', 3);
+        assert_eq!(result.input_remaining(), "\r\ntitle");
+        assert_eq!(result.chomp, "---");
         assert_eq!(result.success, true);
 
-        //multiple eol2
-        let mut parser = Parser::new("\r\n\r\n\r\n\r\n");
+        //fewer
+        parser = Parser::new("--");
         parser.display_errors = false;
-        let result = parser.clone().prim_eols();
-        assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
-        assert_eq!(result.chomp, "\r\n\r\n\r\n\r\n");
+        let result = parser.clone().prim_repeat_char('-', 3);
+        assert_eq!(result.input_remaining(), "--");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        //more, only matches if they are allowed
+        parser = Parser::new("----");
+        parser.display_errors = false;
+        let result = parser.clone().prim_repeat_char('-', 3);
+        assert_eq!(result.input_remaining(), "-");
+        assert_eq!(result.chomp, "---");
         assert_eq!(result.success, true);
+        let result = parser.clone().prim_repeat_char_exactly('-', 3);
+        assert_eq!(result.input_remaining(), "----");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
     }
 
     #[test]
@@ -2301,10 +9247,11 @@ mod tests {
         parser.display_errors = false;
         let result = parser.clone().prim_digit().prim_digit().prim_digit();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "Test");
+        assert_eq!(result.input_remaining(), "Test");
         assert_eq!(result.chomp, "123");
         assert_eq!(result.success, true);
     }
+
     #[test]
     fn test_prim_char() {
         //fail
@@ -2317,7 +9264,7 @@ mod tests {
             .prim_char()
             .prim_char();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, " sting 123");
+        assert_eq!(result.input_remaining(), " sting 123");
         assert_eq!(result.chomp, "Te");
         assert_eq!(result.success, false);
 
@@ -2331,11 +9278,131 @@ mod tests {
             .prim_char()
             .prim_char();
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "ing 123");
+        assert_eq!(result.input_remaining(), "ing 123");
         assert_eq!(result.chomp, "Test");
         assert_eq!(result.success, true);
     }
 
+    #[test]
+    fn test_large_input() {
+        //100KB of digits, consumed by moving the position rather than reallocating the remaining string
+        let input_string = "1234567890".repeat(10000);
+        let mut parser = Parser::new(input_string.as_str());
+        parser.display_errors = false;
+        let result = parser.clone().combi_one_or_more_of(Parser::prim_digit);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.position, 100000);
+        assert_eq!(result.chomp, input_string);
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_large_input_eols() {
+        //100,000 eols, matched without cloning the parser for each one
+        let input_string = format!("{}x", "\r\n".repeat(100000));
+        let mut parser = Parser::new(input_string.as_str());
+        parser.display_errors = false;
+        let result = parser.clone().prim_eols().chomp_clear().prim_char();
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "x");
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn test_prim_indent() {
+        let mut parser = Parser::new("    x");
+        parser.display_errors = false;
+        let result = parser.clone().prim_indent(4);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "x");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, true);
+
+        let result = parser.clone().prim_indent(2);
+        assert_eq!(result.input_remaining(), "    x");
+        assert_eq!(result.success, false);
+
+        //indentation of a later line, using the indents stack
+        parser = Parser::new("a\n  b");
+        parser.display_errors = false;
+        let mut result = parser.clone().prim_char().prim_eol().indent_push(2);
+        let indent = result.indent_current();
+        result = result.prim_indent(indent);
+        assert_eq!(result.input_remaining(), "b");
+        assert_eq!(result.success, true);
+        result = result.indent_pop();
+        assert_eq!(result.indent_current(), 0);
+    }
+
+    #[test]
+    fn test_prim_word_oneof() {
+        let mut parser = Parser::new("int x");
+        parser.display_errors = false;
+        let result = parser.clone().prim_word_oneof(&["int", "in"]);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " x");
+        assert_eq!(result.chomp, "int");
+        assert_eq!(result.success, true);
+
+        //order matters
+        let result = parser.clone().prim_word_oneof(&["in", "int"]);
+        assert_eq!(result.input_remaining(), "t x");
+        assert_eq!(result.chomp, "in");
+        assert_eq!(result.success, true);
+
+        let result = parser.clone().prim_word_oneof(&["float", "str"]);
+        assert_eq!(result.input_remaining(), "int x");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let input_string = "a".repeat(10000);
+        let mut parser = Parser::with_capacity(input_string.as_str(), 10000);
+        parser.display_errors = false;
+        assert_eq!(parser.chomp.capacity() >= 10000, true);
+        let result = parser.clone().combi_one_or_more_of(Parser::prim_char);
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, input_string);
+        assert_eq!(result.success, true);
+
+        //clearing the chomp keeps its capacity
+        let result = result.chomp_clear();
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.chomp.capacity() >= 10000, true);
+    }
+
+    #[test]
+    fn test_prim_word_ci() {
+        let mut parser = Parser::new("TRUE x");
+        parser.display_errors = false;
+        let result = parser.clone().prim_word_ci("true");
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), " x");
+        assert_eq!(result.chomp, "TRUE");
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("tRuE");
+        parser.display_errors = false;
+        let result = parser.clone().prim_word_ci("true");
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "tRuE");
+        assert_eq!(result.success, true);
+
+        parser = Parser::new("tru");
+        parser.display_errors = false;
+        let result = parser.clone().prim_word_ci("true");
+        assert_eq!(result.input_original, parser.input_original);
+        assert_eq!(result.input_remaining(), "tru");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+    }
+
     #[test]
     fn test_prim_word() {
         let parser = Parser::new("Testing 123");
@@ -2346,8 +9413,20 @@ mod tests {
             .prim_word(" ")
             .prim_word("123");
         assert_eq!(result.input_original, parser.input_original);
-        assert_eq!(result.input_remaining, "");
+        assert_eq!(result.input_remaining(), "");
         assert_eq!(result.chomp, "Testing 123");
         assert_eq!(result.success, true);
+
+        //the length of "ab" ends in the middle of the 2 byte "é", so fails rather than panics
+        let parser = Parser::new("aé");
+        let result = parser.clone().prim_word("ab");
+        assert_eq!(result.input_remaining(), "aé");
+        assert_eq!(result.chomp, "");
+        assert_eq!(result.success, false);
+
+        let result = parser.clone().prim_word("a").prim_word("é");
+        assert_eq!(result.input_remaining(), "");
+        assert_eq!(result.chomp, "aé");
+        assert_eq!(result.success, true);
     }
 }